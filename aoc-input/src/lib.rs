@@ -0,0 +1,194 @@
+//! The key the personal inputs are encrypted with, shared by the runner, which
+//! decrypts them, and the generator, which encrypts them as it downloads them.
+//!
+//! An encrypted input is the input encrypted with XChaCha20-Poly1305, with the
+//! random nonce in front of it, in a file named like the plain input with
+//! `.enc` on the end.
+
+#![deny(missing_docs)]
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+
+/// The environment variable with the key, which comes before the key file.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// The file with the key, relative to the repo.
+pub const KEY_FILE: &str = ".input-key";
+
+/// The extension added to the name of an input once it is encrypted.
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// The length of the nonce at the start of an encrypted input.
+const NONCE_LEN: usize = 24;
+
+/// The key the inputs are encrypted with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Generate a new random key.
+    pub fn generate() -> Self {
+        Key(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// Parse the key from hex.
+    pub fn parse(s: &str) -> Result<Self> {
+        let bytes = hex::decode(s.trim())
+            .map_err(|e| anyhow::anyhow!("Invalid key: {}", e))?;
+        let key = bytes.as_slice().try_into().map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid key: expected 32 bytes, got {}",
+                bytes.len()
+            )
+        })?;
+
+        Ok(Key(key))
+    }
+
+    /// Load the key from the environment, or the key file in the repo. An
+    /// empty variable counts as unset, since that's what CI gets for a
+    /// secret that isn't there, like on a pull request from a fork.
+    pub fn load(repo_dir: &Path) -> Result<Option<Self>> {
+        match std::env::var(KEY_VAR) {
+            Ok(key) if !key.trim().is_empty() => {
+                return Key::parse(&key)
+                    .map(Some)
+                    .map_err(|e| anyhow::anyhow!("{} in {}", e, KEY_VAR));
+            }
+            _ => {}
+        }
+
+        let path = repo_dir.join(KEY_FILE);
+        match std::fs::read_to_string(&path) {
+            Ok(key) => Key::parse(&key)
+                .map(Some)
+                .map_err(|e| anyhow::anyhow!("{} in {}", e, path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => anyhow::bail!("Failed to read {}: {}", path.display(), e),
+        }
+    }
+
+    /// Load the key, or generate one and save it in the key file of the repo
+    /// if there is none yet.
+    pub fn load_or_create(repo_dir: &Path) -> Result<Self> {
+        if let Some(key) = Key::load(repo_dir)? {
+            return Ok(key);
+        }
+
+        let key = Key::generate();
+        let path = repo_dir.join(KEY_FILE);
+        std::fs::write(&path, format!("{}\n", key.to_hex()))?;
+        println!(
+            "Created a new key for the inputs in {}, keep a copy of it \
+             somewhere safe",
+            path.display()
+        );
+
+        Ok(key)
+    }
+
+    /// The key in hex.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Encrypt the input, with a random nonce in front of it.
+    pub fn encrypt(&self, input: &str) -> Result<Vec<u8>> {
+        let cipher = XChaCha20Poly1305::new(&self.0.into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let encrypted = cipher
+            .encrypt(&nonce, input.as_bytes())
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt the input"))?;
+
+        Ok([nonce.as_slice(), &encrypted].concat())
+    }
+
+    /// Decrypt an input encrypted with the key.
+    pub fn decrypt(&self, encrypted: &[u8]) -> Result<String> {
+        if encrypted.len() < NONCE_LEN {
+            anyhow::bail!("Encrypted input is too short");
+        }
+        let (nonce, encrypted) = encrypted.split_at(NONCE_LEN);
+        let cipher = XChaCha20Poly1305::new(&self.0.into());
+        let input = cipher
+            .decrypt(XNonce::from_slice(nonce), encrypted)
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Failed to decrypt the input, is it the right key?"
+                )
+            })?;
+
+        Ok(String::from_utf8(input)?)
+    }
+}
+
+/// Keep the key out of the logs.
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+/// The path of the encrypted input for the path of the plain one.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::Key;
+
+    #[test]
+    fn test_encrypt() {
+        let key = Key::generate();
+        let encrypted = key.encrypt("1abc2\npqr3stu8vwx\n").unwrap();
+        assert!(!String::from_utf8_lossy(&encrypted).contains("1abc2"));
+        assert_eq!(key.decrypt(&encrypted).unwrap(), "1abc2\npqr3stu8vwx\n");
+
+        // Each encryption has its own nonce.
+        assert_ne!(
+            key.encrypt("1abc2").unwrap(),
+            key.encrypt("1abc2").unwrap()
+        );
+
+        // The wrong key, or a changed input, fail to decrypt.
+        assert!(Key::generate().decrypt(&encrypted).is_err());
+        let mut changed = encrypted.clone();
+        *changed.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt(&changed).is_err());
+        assert!(key.decrypt(&[0; 10]).is_err());
+    }
+
+    #[test]
+    fn test_parse_key() {
+        let key = Key::generate();
+        assert_eq!(Key::parse(&format!("{}\n", key.to_hex())).unwrap(), key);
+        assert_eq!(format!("{:?}", key), "Key(..)");
+
+        assert!(Key::parse("not hex").is_err());
+        assert!(Key::parse("abcd").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_path() {
+        assert_eq!(
+            super::encrypted_path(std::path::Path::new("input/2023/day01.txt")),
+            std::path::Path::new("input/2023/day01.txt.enc")
+        );
+    }
+}
@@ -1,7 +1,7 @@
-#[cfg(not(target_os = "macos"))]
+#[cfg(feature = "cuda")]
 use cuda_builder::CudaBuilder;
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(feature = "cuda")]
 fn main() {
     let gpu_dir = std::env::current_dir().unwrap().join("gpu");
     let res_dir = std::env::current_dir().unwrap().join("resources");
@@ -11,5 +11,5 @@ fn main() {
         .unwrap();
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(not(feature = "cuda"))]
 fn main() {}
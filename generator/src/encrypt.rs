@@ -0,0 +1,83 @@
+//! Encrypt the inputs that were checked in before they were encrypted.
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+/// Encrypt the plain inputs for the year, or every year, with the key for the
+/// repo, creating the key if there is none yet. Each input is replaced by the
+/// encrypted one once it decrypts back to the same input. The answers and the
+/// history of submissions stay as they are.
+///
+/// Returns the encrypted inputs, in order.
+pub fn encrypt_inputs(
+    working_dir: &Path,
+    year: Option<u32>,
+) -> Result<Vec<PathBuf>> {
+    let input_dir = working_dir.join(crate::INPUT_DIR);
+    let year_dirs = match year {
+        Some(year) => vec![input_dir.join(year.to_string())],
+        None => {
+            let mut dirs = Vec::new();
+            for entry in std::fs::read_dir(&input_dir).map_err(|e| {
+                anyhow::anyhow!("Failed to read {}: {}", input_dir.display(), e)
+            })? {
+                let path = entry?.path();
+                let is_year = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.parse::<u32>().is_ok());
+                if path.is_dir() && is_year {
+                    dirs.push(path);
+                }
+            }
+            dirs
+        }
+    };
+
+    let mut inputs = Vec::new();
+    for year_dir in year_dirs {
+        for entry in std::fs::read_dir(&year_dir).map_err(|e| {
+            anyhow::anyhow!("Failed to read {}: {}", year_dir.display(), e)
+        })? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|name| name.to_str());
+            // The inputs are `dayNN.txt`, or `dayNN-name.txt` for someone
+            // else's, next to the answers and the history of submissions.
+            let is_input = matches!(name, Some(name) if name.starts_with("day")
+                && name.ends_with(".txt"));
+            if is_input {
+                inputs.push(path);
+            }
+        }
+    }
+    inputs.sort();
+
+    let mut encrypted_paths = Vec::new();
+    if inputs.is_empty() {
+        return Ok(encrypted_paths);
+    }
+    let key = aoc_input::Key::load_or_create(working_dir)?;
+    for path in inputs {
+        let encrypted_path = aoc_input::encrypted_path(&path);
+        if encrypted_path.exists() {
+            anyhow::bail!(
+                "Both {} and {} exist, remove one of them",
+                path.display(),
+                encrypted_path.display()
+            );
+        }
+
+        let plain = std::fs::read_to_string(&path)?;
+        let encrypted = key.encrypt(&plain)?;
+        if key.decrypt(&encrypted)? != plain {
+            anyhow::bail!("Failed to encrypt {}", path.display());
+        }
+
+        println!("Encrypting {}", path.display());
+        std::fs::write(&encrypted_path, encrypted)?;
+        std::fs::remove_file(&path)?;
+        encrypted_paths.push(encrypted_path);
+    }
+
+    Ok(encrypted_paths)
+}
@@ -0,0 +1,134 @@
+//! Pull the examples and their answers out of the puzzle, for the tests.
+use anyhow::Result;
+
+use crate::puzzle::Part;
+
+/// An example from the puzzle, and the answer for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// The example input.
+    pub input: String,
+    /// The answer for the example input.
+    pub answer: String,
+}
+
+impl Example {
+    /// Find the example for a part of the puzzle: the first code block, and
+    /// the last highlighted number outside the code blocks, which is the
+    /// answer to the question at the end. Part two often reuses the example
+    /// from part one, so that is used if the part has no code block of its own.
+    pub fn find(
+        part: &Part,
+        fallback_input: Option<&str>,
+    ) -> Result<Option<Self>> {
+        let input = match part.first_code_block().or(fallback_input) {
+            Some(input) => input.to_string(),
+            None => return Ok(None),
+        };
+        let re = regex::Regex::new(r"`\*(-?[0-9]+)\*`")?;
+        let answer = match part
+            .text()
+            .iter()
+            .flat_map(|text| re.captures_iter(text))
+            .last()
+        {
+            Some(captures) => captures[1].to_string(),
+            None => return Ok(None),
+        };
+
+        Ok(Some(Example { input, answer }))
+    }
+}
+
+/// Get the type for the answer of a part, which is big enough for the answer
+/// to the example. Answers are usually small, but not always positive.
+pub fn answer_type(example: Option<&Example>) -> &'static str {
+    match example.map(|example| example.answer.parse::<i128>()) {
+        Some(Ok(answer)) if answer < 0 => "i64",
+        Some(Ok(answer)) if answer > u32::MAX as i128 => "u64",
+        _ => "u32",
+    }
+}
+
+/// The lines of the test for a part that check the example.
+pub fn assertion(part: u32, example: &Example) -> String {
+    format!(
+        "        let input = {};\n        \
+         assert_eq!(super::solve_part_{}(input).unwrap(), {});\n",
+        raw_string(&example.input),
+        part,
+        example.answer
+    )
+}
+
+/// Quote the string as a raw string literal, with enough `#`s that it can't
+/// end early.
+fn raw_string(s: &str) -> String {
+    let mut hashes = "#".to_string();
+    while s.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+
+    format!("r{}\"{}\"{}", hashes, s, hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::puzzle::Part;
+
+    #[test]
+    fn test_find() {
+        let part_one = Part::parse(
+            r#"For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and `38`. Adding these
+together produces `*50*`.
+
+Your puzzle answer was `54450`.
+"#,
+        )
+        .unwrap();
+        let example = super::Example::find(&part_one, None).unwrap().unwrap();
+        assert_eq!(example.input, "1abc2\npqr3stu8vwx");
+        assert_eq!(example.answer, "50");
+        assert_eq!(super::answer_type(Some(&example)), "u32");
+
+        // Part two can reuse the example from part one.
+        let part_two =
+            Part::parse("Now the answer is `*-2*` instead.").unwrap();
+        let example = super::Example::find(&part_two, Some("1abc2"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(example.input, "1abc2");
+        assert_eq!(example.answer, "-2");
+        assert_eq!(super::answer_type(Some(&example)), "i64");
+        assert_eq!(super::Example::find(&part_two, None).unwrap(), None);
+
+        // Without a highlighted answer, there's nothing to test, and one in a
+        // code block isn't the answer.
+        let part = Part::parse("```\n`*1*`\n```").unwrap();
+        assert_eq!(super::Example::find(&part, None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_assertion() {
+        let example = super::Example {
+            input: "a \"# b".to_string(),
+            answer: "15726453850399".to_string(),
+        };
+        assert_eq!(super::answer_type(Some(&example)), "u64");
+        assert_eq!(
+            super::assertion(2, &example),
+            r####"        let input = r##"a "# b"##;
+        assert_eq!(super::solve_part_2(input).unwrap(), 15726453850399);
+"####
+        );
+    }
+}
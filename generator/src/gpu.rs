@@ -0,0 +1,75 @@
+//! Scaffold the GPU kernel for a day and the host code that launches it.
+use anyhow::Result;
+
+use crate::{template::Templates, Puzzle};
+
+/// Generate the kernel for the day in `gpu/src/yYYYY/dayNN.rs` and the host
+/// code for it in `src/yYYYY/gpu_dayNN.rs`, and register them. Files that
+/// already exist are left alone.
+///
+/// The title comes from the cached puzzle, so the day has to be generated
+/// first.
+pub fn generate_gpu(
+    working_dir: &std::path::Path,
+    year: u32,
+    day: u32,
+) -> Result<()> {
+    let puzzle_path = crate::puzzle_path(working_dir, year, day)?;
+    let puzzle_markdown =
+        std::fs::read_to_string(&puzzle_path).map_err(|e| {
+            anyhow::anyhow!("Failed to read {}: {}", puzzle_path.display(), e)
+        })?;
+    let puzzle = Puzzle::parse(&puzzle_markdown)?;
+
+    let gpu_src_dir = working_dir.join("gpu").join("src");
+    if !gpu_src_dir.exists() {
+        anyhow::bail!(
+            "GPU source directory does not exist: {}",
+            gpu_src_dir.display()
+        );
+    }
+
+    // The kernel copies its input to its output to start with, and the host
+    // code launches it on the GPU or in the emulator.
+    let templates = Templates::new(working_dir);
+    let values = [
+        ("year", year.to_string()),
+        ("day", day.to_string()),
+        ("day:02", format!("{:02}", day)),
+        ("title_literal", format!("{:?}", puzzle.title)),
+        ("title", puzzle.title),
+    ];
+    let values = values
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect::<Vec<_>>();
+
+    let module = crate::register::year_module(year);
+    let kernel_dir = gpu_src_dir.join(&module);
+    std::fs::create_dir_all(&kernel_dir)?;
+    write_new(
+        &kernel_dir.join(format!("day{:02}.rs", day)),
+        &templates.render("kernel.rs", &values)?,
+    )?;
+    let host_dir = working_dir.join("src").join(&module);
+    std::fs::create_dir_all(&host_dir)?;
+    write_new(
+        &host_dir.join(format!("gpu_day{:02}.rs", day)),
+        &templates.render("gpu_day.rs", &values)?,
+    )?;
+
+    crate::register::register(working_dir, year, day)
+}
+
+/// Write the file, unless it already exists.
+fn write_new(path: &std::path::Path, contents: &str) -> Result<()> {
+    if path.exists() {
+        println!("Keeping existing file at {}", path.display());
+        return Ok(());
+    }
+
+    println!("Writing template to {}", path.display());
+    std::fs::write(path, contents.as_bytes())?;
+
+    Ok(())
+}
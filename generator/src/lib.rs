@@ -0,0 +1,301 @@
+//! Generate a template for a new day and get the input for that day.
+//!
+//! The puzzles and inputs come from a [`PuzzleSource`], which is Advent of Code
+//! itself, or files on disk for working offline and for the tests. Answers go
+//! to a [`Submitter`], which is Advent of Code or a local server standing in
+//! for it.
+
+#![deny(missing_docs)]
+
+mod encrypt;
+mod example;
+mod gpu;
+mod puzzle;
+mod refresh;
+mod register;
+mod source;
+mod submit;
+mod template;
+
+use anyhow::Result;
+
+use crate::example::Example;
+pub use crate::{
+    encrypt::encrypt_inputs,
+    gpu::generate_gpu,
+    puzzle::{Block, Part, Puzzle},
+    register::{register_module, register_solution},
+    source::{AocSource, FileSource, PuzzleSource},
+    submit::{
+        history_path, runner_answer, submit, Attempt, History, LocalServer,
+        Outcome, Submitter,
+    },
+    template::{Templates, TEMPLATE_DIR},
+};
+
+/// The output width of the text.
+const OUTPUT_WIDTH: usize = 80;
+
+/// The directory, relative to the repo, the puzzle descriptions are cached in.
+pub const PUZZLE_DIR: &str = "puzzles";
+
+/// The directory, relative to the repo, the inputs are saved in.
+pub const INPUT_DIR: &str = "input";
+
+/// Get the input and puzzle for the day from the source, cache them in the
+/// repo and generate the template for the day, or refresh its doc comments if
+/// it already exists. The module and solution for the day are registered in
+/// the module for the year and `src/lib.rs`.
+///
+/// Each year has its own directory: `src/yYYYY/dayNN.rs`,
+/// `input/YYYY/dayNN.txt.enc` and `puzzles/YYYY/dayNN.md`.
+pub fn generate(
+    source: &dyn PuzzleSource,
+    working_dir: &std::path::Path,
+    year: u32,
+    day: u32,
+) -> Result<()> {
+    // Make sure the day is unlocked.
+    if !source.day_unlocked(year, day)? {
+        anyhow::bail!("{} day {} is not unlocked", year, day);
+    }
+
+    // Get the input for the day.
+    get_input(source, working_dir, year, day)?;
+
+    // Always refetch the puzzle, since part two only shows up once part one
+    // is solved.
+    let puzzle_markdown = source.puzzle_markdown(year, day)?;
+    let puzzle_path = puzzle_path(working_dir, year, day)?;
+    println!("Writing puzzle to {}", puzzle_path.display());
+    std::fs::write(&puzzle_path, puzzle_markdown.as_bytes())?;
+
+    // Generate the template for the day, and register it.
+    generate_template(working_dir, year, day, &puzzle_markdown)?;
+    register::register(working_dir, year, day)
+}
+
+/// Get the path of the cached puzzle markdown for the day, creating the cache
+/// directory for the year if it doesn't exist yet.
+pub fn puzzle_path(
+    working_dir: &std::path::Path,
+    year: u32,
+    day: u32,
+) -> Result<std::path::PathBuf> {
+    let puzzle_dir = working_dir.join(PUZZLE_DIR).join(year.to_string());
+    std::fs::create_dir_all(&puzzle_dir)?;
+
+    Ok(puzzle_dir.join(format!("day{:02}.md", day)))
+}
+
+/// Get the input for the day and save it to our inputs folder, encrypted with
+/// the key for the repo, unless it is already there. The input for a day
+/// never changes, so there's no need to fetch it twice.
+fn get_input(
+    source: &dyn PuzzleSource,
+    working_dir: &std::path::Path,
+    year: u32,
+    day: u32,
+) -> Result<()> {
+    let input_dir = working_dir.join(INPUT_DIR);
+
+    if !input_dir.exists() {
+        anyhow::bail!(
+            "Input directory does not exist: {}",
+            input_dir.display()
+        );
+    }
+
+    let year_dir = input_dir.join(year.to_string());
+    std::fs::create_dir_all(&year_dir)?;
+    let input_path = year_dir.join(format!("day{:02}.txt", day));
+    let encrypted_path = aoc_input::encrypted_path(&input_path);
+
+    for path in [&input_path, &encrypted_path] {
+        if path.exists() {
+            println!("Using cached input at {}", path.display());
+            return Ok(());
+        }
+    }
+
+    let input = source.input(year, day)?;
+    let key = aoc_input::Key::load_or_create(working_dir)?;
+
+    println!("Writing encrypted input to {}", encrypted_path.display());
+
+    std::fs::write(encrypted_path, key.encrypt(&input)?)?;
+
+    Ok(())
+}
+
+/// Generate the template for the day.
+fn generate_template(
+    working_dir: &std::path::Path,
+    year: u32,
+    day: u32,
+    puzzle_markdown: &str,
+) -> Result<()> {
+    // Parse the puzzle markdown.
+    let puzzle = Puzzle::parse(puzzle_markdown)?;
+    let templates = Templates::new(working_dir);
+
+    let src_dir = working_dir.join("src");
+
+    if !src_dir.exists() {
+        anyhow::bail!("Source directory does not exist: {}", src_dir.display());
+    }
+
+    let year_dir = src_dir.join(register::year_module(year));
+    std::fs::create_dir_all(&year_dir)?;
+    let template_path = year_dir.join(format!("day{:02}.rs", day));
+
+    if template_path.exists() {
+        // If the template already exists, let's try and fix the template and
+        // add information where we can.
+
+        // Read the existing file.
+        let existing = std::fs::read_to_string(&template_path)?;
+
+        // Replace the first line with the title, keeping the rest of the
+        // file as it is.
+        let replaced_title = format!(
+            "//!  Day {:02}: {}\n{}",
+            day,
+            puzzle.title,
+            existing.split_once('\n').map_or("", |(_, rest)| rest)
+        );
+
+        let replaced_part_one_text =
+            refresh::replace_doc_comment(&replaced_title, 1, &puzzle.doc(1)?)?;
+
+        let replaced_part_two_text = refresh::replace_doc_comment(
+            &replaced_part_one_text,
+            2,
+            &puzzle.doc(2)?,
+        )?;
+
+        // Add the examples to the tests of parts that haven't been started.
+        let with_part_one_example = replace_unstarted_part(
+            &templates,
+            &replaced_part_two_text,
+            year,
+            day,
+            1,
+            puzzle.example(1)?.as_ref(),
+        )?;
+        let with_part_two_example = replace_unstarted_part(
+            &templates,
+            &with_part_one_example,
+            year,
+            day,
+            2,
+            puzzle.example(2)?.as_ref(),
+        )?;
+
+        // Write the updated template to the file.
+        println!("Updating file at {}", template_path.display());
+        std::fs::write(template_path, with_part_two_example.as_bytes())?;
+    } else {
+        // Fill in the template.
+        let part_one_example = puzzle.example(1)?;
+        let part_two_example = puzzle.example(2)?;
+        let part_one_example = part_one_example.as_ref();
+        let part_two_example = part_two_example.as_ref();
+        let part_one_type = example::answer_type(part_one_example);
+        let part_two_type = example::answer_type(part_two_example);
+        let template = templates.render(
+            "day.rs",
+            &[
+                ("year", &year.to_string()),
+                ("day", &day.to_string()),
+                ("day:02", &format!("{:02}", day)),
+                ("title", &puzzle.title),
+                ("title_literal", &format!("{:?}", puzzle.title)),
+                ("part_one", &part_doc(&puzzle.doc(1)?)),
+                ("part_two", &part_doc(&puzzle.doc(2)?)),
+                ("part_one_type", part_one_type),
+                ("part_two_type", part_two_type),
+                ("part_one_fn", &templates.solve_fn(1, part_one_type)?),
+                ("part_two_fn", &templates.solve_fn(2, part_two_type)?),
+                (
+                    "part_one_test",
+                    &templates.test_fn(year, day, 1, part_one_example)?,
+                ),
+                (
+                    "part_two_test",
+                    &templates.test_fn(year, day, 2, part_two_example)?,
+                ),
+            ],
+        )?;
+
+        // Write the template to the file.
+        println!("Writing template to {}", template_path.display());
+        std::fs::write(template_path, template.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// The text of the doc comment for a part, or a note that the part isn't
+/// unlocked yet.
+fn part_doc(text: &str) -> String {
+    if text.trim().is_empty() {
+        "Not yet unlocked.".to_string()
+    } else {
+        doc_comment(text)
+    }
+}
+
+/// Continue the text of a doc comment onto the lines after its first.
+fn doc_comment(text: &str) -> String {
+    text.replace('\n', "\n/// ").replace("\n/// \n", "\n///\n")
+}
+
+/// Add the example to the test of a part, if the part and its test are still
+/// the ones from the template, and use a type for the answer that fits it.
+fn replace_unstarted_part(
+    templates: &Templates,
+    existing: &str,
+    year: u32,
+    day: u32,
+    part: u32,
+    example: Option<&Example>,
+) -> Result<String> {
+    let example = match example {
+        Some(example) => example,
+        None => return Ok(existing.to_string()),
+    };
+    let unstarted_fn = templates.solve_fn(part, "u32")?;
+    let unstarted_test = templates.test_fn(year, day, part, None)?;
+    if !existing.contains(&unstarted_fn) || !existing.contains(&unstarted_test)
+    {
+        return Ok(existing.to_string());
+    }
+
+    Ok(existing
+        .replace(
+            &unstarted_fn,
+            &templates.solve_fn(part, example::answer_type(Some(example)))?,
+        )
+        .replace(
+            &unstarted_test,
+            &templates.test_fn(year, day, part, Some(example))?,
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_puzzle_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let working_dir = temp.path();
+        let path = super::puzzle_path(working_dir, 2023, 3).unwrap();
+        assert_eq!(
+            path,
+            working_dir.join("puzzles").join("2023").join("day03.md")
+        );
+        assert!(working_dir.join("puzzles").join("2023").is_dir());
+    }
+}
@@ -0,0 +1,351 @@
+//! Parse the puzzle markdown from Advent of Code into its title and parts,
+//! and the paragraphs, code blocks, lists and answers of each part.
+use anyhow::Result;
+
+use crate::example::Example;
+
+/// A puzzle, as it is on the page for the day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The day of the puzzle.
+    pub day: u32,
+    /// The title, without the day.
+    pub title: String,
+    /// Part one, and part two once it is unlocked.
+    pub parts: Vec<Part>,
+}
+
+/// A part of a puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Part {
+    /// The text of the part, without the answer and what comes after it.
+    pub blocks: Vec<Block>,
+    /// Our answer, once the part is solved.
+    pub answer: Option<String>,
+}
+
+/// A block of the text of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// A paragraph, with its line breaks.
+    Paragraph(String),
+    /// The contents of a code block.
+    Code(String),
+    /// The items of a list, each with its line breaks.
+    List(Vec<String>),
+}
+
+/// Where the text of a part ends: the answer, the form to submit it, or the
+/// link to the input.
+const END_OF_TEXT: &[&str] = &[
+    "Your puzzle answer was",
+    "Answer:",
+    "To begin, [get your puzzle input]",
+    "Although it hasn't changed",
+    "Both parts of this puzzle are complete",
+];
+
+impl Puzzle {
+    /// Parse the puzzle markdown.
+    pub fn parse(markdown: &str) -> Result<Self> {
+        let mut lines = markdown.trim().lines();
+
+        // The title is in the heading on the first line, like
+        // `\--- Day 7: Camel Cards ---`.
+        let heading = lines.next().unwrap_or("");
+        let (day, title) = heading
+            .trim_start_matches('\\')
+            .strip_prefix("--- Day ")
+            .and_then(|heading| heading.strip_suffix(" ---"))
+            .and_then(|heading| heading.split_once(": "))
+            .ok_or_else(|| anyhow::anyhow!("Invalid title: {}", heading))?;
+        let day = day.parse()?;
+        let title = title.trim().to_string();
+
+        // Each part starts with a heading, and the first is the title.
+        let mut sections = vec![Vec::new()];
+        for line in lines {
+            if is_heading(line, "--- Part Two ---") {
+                sections.push(Vec::new());
+            } else if let Some(section) = sections.last_mut() {
+                section.push(line);
+            }
+        }
+        let parts = sections
+            .iter()
+            .map(|section| Part::parse(&section.join("\n")))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Puzzle { day, title, parts })
+    }
+
+    /// Get a part of the puzzle, if it is unlocked.
+    pub fn part(&self, part: u32) -> Option<&Part> {
+        self.parts.get((part as usize).checked_sub(1)?)
+    }
+
+    /// Get the text of a part for a doc comment, empty if it isn't unlocked.
+    pub fn doc(&self, part: u32) -> Result<String> {
+        self.part(part).map_or_else(|| Ok(String::new()), Part::doc)
+    }
+
+    /// Find the example for a part, if it has one with an answer. Part two
+    /// falls back on the input of the example for part one.
+    pub fn example(&self, part: u32) -> Result<Option<Example>> {
+        let fallback = match part {
+            1 => None,
+            _ => self.part(1).and_then(Part::first_code_block),
+        };
+
+        match self.part(part) {
+            Some(text) => Example::find(text, fallback),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Part {
+    /// Parse the markdown for a part, after its heading.
+    pub fn parse(markdown: &str) -> Result<Self> {
+        let mut part = Part::default();
+        let mut lines = markdown.lines().peekable();
+        let mut ended = false;
+        while let Some(line) = lines.next() {
+            if line.trim().is_empty() || line.chars().all(|c| c == '-') {
+                continue;
+            }
+
+            let block = if line.trim_end() == "```" {
+                let mut code = Vec::new();
+                loop {
+                    match lines.next() {
+                        Some(line) if line.trim_end() == "```" => break,
+                        Some(line) => code.push(line),
+                        None => anyhow::bail!("Unclosed code block"),
+                    }
+                }
+                Block::Code(code.join("\n"))
+            } else if is_list_item(line) {
+                let mut items = vec![list_item(line).to_string()];
+                while let Some(line) = lines.peek() {
+                    if is_list_item(line) {
+                        items.push(list_item(line).to_string());
+                    } else if line.starts_with(' ') && !line.trim().is_empty() {
+                        if let Some(item) = items.last_mut() {
+                            item.push('\n');
+                            item.push_str(line.trim());
+                        }
+                    } else {
+                        break;
+                    }
+                    lines.next();
+                }
+
+                // Empty items are left over from the markup.
+                items.retain(|item| !item.is_empty());
+                Block::List(items)
+            } else {
+                let mut paragraph = vec![line];
+                while let Some(line) = lines.peek() {
+                    if line.trim().is_empty() || line.trim_end() == "```" {
+                        break;
+                    }
+                    paragraph.push(line);
+                    lines.next();
+                }
+                Block::Paragraph(paragraph.join("\n"))
+            };
+
+            // Everything after the end of the text is about our answer.
+            if let Block::Paragraph(paragraph) = &block {
+                if let Some(answer) =
+                    paragraph.strip_prefix("Your puzzle answer was")
+                {
+                    let answer = answer.trim().trim_end_matches('.');
+                    part.answer = Some(answer.trim_matches('`').to_string());
+                }
+                if END_OF_TEXT
+                    .iter()
+                    .any(|phrase| paragraph.starts_with(phrase))
+                {
+                    ended = true;
+                }
+            }
+            if !ended {
+                part.blocks.push(block);
+            }
+        }
+
+        Ok(part)
+    }
+
+    /// Get the first code block, which is usually the example.
+    pub fn first_code_block(&self) -> Option<&str> {
+        self.blocks.iter().find_map(|block| match block {
+            Block::Code(code) => Some(code.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Get the text outside the code blocks, in order.
+    pub fn text(&self) -> Vec<&str> {
+        self.blocks
+            .iter()
+            .flat_map(|block| match block {
+                Block::Paragraph(paragraph) => vec![paragraph.as_str()],
+                Block::List(items) => {
+                    items.iter().map(String::as_str).collect()
+                }
+                Block::Code(_) => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Get the text of the part as markdown for a doc comment, with the links
+    /// as doc links and the code blocks ignored by doc tests.
+    pub fn doc(&self) -> Result<String> {
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph(paragraph) => doc_links(paragraph),
+                Block::Code(code) => Ok(format!("```ignore\n{}\n```", code)),
+                Block::List(items) => Ok(items
+                    .iter()
+                    .map(|item| {
+                        Ok(format!(
+                            "* {}",
+                            doc_links(item)?.replace('\n', "\n  ")
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join("\n")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(blocks.join("\n\n"))
+    }
+}
+
+/// Whether the line is the heading, with or without the escape in front.
+fn is_heading(line: &str, heading: &str) -> bool {
+    line.trim().trim_start_matches('\\') == heading
+}
+
+/// Whether the line starts an item of a list.
+fn is_list_item(line: &str) -> bool {
+    line.starts_with("* ") || line.trim_end() == "*"
+}
+
+/// Get the text of the line that starts an item of a list.
+fn list_item(line: &str) -> &str {
+    line.trim_start_matches('*').trim()
+}
+
+/// Replace the markdown links with doc links, making the links to Advent of
+/// Code absolute.
+fn doc_links(text: &str) -> Result<String> {
+    let re = regex::Regex::new(r"\[([^]]+)]\(([^)]+)\)")?;
+    let text = text.replace("](/", "](https://adventofcode.com/");
+
+    Ok(re.replace_all(&text, "$1 (<$2>)").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{Block, Part, Puzzle};
+
+    #[test]
+    fn test_parse_title() {
+        let puzzle =
+            Puzzle::parse("\\--- Day 7: Camel-Cards: Redux ---\n----------\n")
+                .unwrap();
+        assert_eq!(puzzle.day, 7);
+        assert_eq!(puzzle.title, "Camel-Cards: Redux");
+        assert_eq!(puzzle.parts, vec![Part::default()]);
+
+        let puzzle = Puzzle::parse("--- Day 25: Snowverload ---").unwrap();
+        assert_eq!(puzzle.day, 25);
+        assert_eq!(puzzle.title, "Snowverload");
+        assert_eq!(puzzle.part(2), None);
+        assert_eq!(puzzle.doc(2).unwrap(), "");
+
+        assert!(Puzzle::parse("Day 7: Camel Cards").is_err());
+        assert!(Puzzle::parse("").is_err());
+    }
+
+    #[test]
+    fn test_parse_part() {
+        let part = Part::parse(
+            r#"----------
+
+A [link](/2023/about) and
+a [wiki link](https://en.wikipedia.org/wiki/Joker_(playing_card)).
+
+```
+1 2
+
+3 4
+```
+
+* One item
+  over two lines.
+*
+* Two `*2*`.
+
+Your puzzle answer was `-5`.
+
+Both parts of this puzzle are complete! They provide two gold stars: \*\*
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            part.blocks,
+            vec![
+                Block::Paragraph(
+                    "A [link](/2023/about) and\na [wiki link](https://en.\
+                     wikipedia.org/wiki/Joker_(playing_card))."
+                        .to_string()
+                ),
+                Block::Code("1 2\n\n3 4".to_string()),
+                Block::List(vec![
+                    "One item\nover two lines.".to_string(),
+                    "Two `*2*`.".to_string()
+                ]),
+            ]
+        );
+        assert_eq!(part.answer.as_deref(), Some("-5"));
+        assert_eq!(part.first_code_block(), Some("1 2\n\n3 4"));
+        assert_eq!(
+            part.doc().unwrap(),
+            r#"A link (<https://adventofcode.com/2023/about>) and
+a wiki link (<https://en.wikipedia.org/wiki/Joker_(playing_card>)).
+
+```ignore
+1 2
+
+3 4
+```
+
+* One item
+  over two lines.
+* Two `*2*`."#
+        );
+
+        // An unsolved part ends at the form for the answer.
+        let part = Part::parse(
+            "Solve it.\n\nTo begin, [get your puzzle input](/2023/day/1/input).\
+             \n\nAnswer:\n",
+        )
+        .unwrap();
+        assert_eq!(
+            part.blocks,
+            vec![Block::Paragraph("Solve it.".to_string())]
+        );
+        assert_eq!(part.answer, None);
+
+        assert!(Part::parse("```\n1\n").is_err());
+    }
+}
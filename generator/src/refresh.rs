@@ -0,0 +1,155 @@
+//! Refresh the doc comments of a day that was already generated, by parsing
+//! the Rust file to find them. Everything else in the file is left as it is.
+use std::ops::Range;
+
+use anyhow::Result;
+use syn::spanned::Spanned;
+
+/// Replace the doc comment on the solve function for the part with the text
+/// of the part, or a note that the part isn't unlocked yet.
+///
+/// The doc comment is wherever the doc attributes on the function are, even
+/// if there are other attributes around them. If the function has no doc
+/// comment, it goes above the function and its attributes.
+pub fn replace_doc_comment(
+    source: &str,
+    part: u32,
+    text: &str,
+) -> Result<String> {
+    let file = syn::parse_file(source)
+        .map_err(|e| anyhow::anyhow!("Failed to parse the day: {}", e))?;
+
+    // Find the function among the items at the top of the file.
+    let name = format!("solve_part_{}", part);
+    let function = file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            syn::Item::Fn(function) if function.sig.ident == name => {
+                Some(function)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("Missing part {} function", part))?;
+
+    // The lines of each doc attribute, which is a whole comment for `///`.
+    let docs = function
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .map(|attr| lines(attr.span()))
+        .collect::<Vec<_>>();
+    let insert_at = match docs.first() {
+        Some(doc) => doc.start,
+        None => lines(function.span()).start,
+    };
+
+    let comment = format!("/// {}", crate::part_doc(text));
+
+    // Keep the indentation of the line the comment goes on.
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let indent = lines
+        .get(insert_at)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .unwrap_or("");
+    let comment = comment
+        .lines()
+        .map(|line| format!("{}{}", indent, line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Take the old doc comment out from the bottom up, so the lines above
+    // stay where they are.
+    for doc in docs.iter().rev() {
+        lines.drain(doc.clone());
+    }
+    lines.insert(insert_at, &comment);
+
+    let mut refreshed = lines.join("\n");
+    if source.ends_with('\n') {
+        refreshed.push('\n');
+    }
+
+    Ok(refreshed)
+}
+
+/// The lines of the source the span covers, counting from zero.
+fn lines(span: proc_macro2::Span) -> Range<usize> {
+    span.start().line - 1..span.end().line
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::replace_doc_comment;
+
+    #[test]
+    fn test_replace_doc_comment() {
+        // The text of part one is in the comment for part two as well, and
+        // the functions have attributes and their own layout.
+        let source = r#"//!  Day 01: Trebuchet?!
+use anyhow::Result;
+
+/// The same text.
+pub fn solve_part_1(input: &str) -> Result<u32> {
+    Ok(input.len() as u32)
+}
+
+#[allow(clippy::needless_range_loop)]
+/// The same text.
+///
+/// And more.
+#[inline]
+pub fn solve_part_2(
+    input: &str,
+) -> Result<u32> {
+    /// Not this one.
+    fn inner() {}
+    Ok(0)
+}
+"#;
+        let refreshed =
+            replace_doc_comment(source, 2, "New text.\n\n`*2*`").unwrap();
+        assert_eq!(
+            refreshed,
+            r#"//!  Day 01: Trebuchet?!
+use anyhow::Result;
+
+/// The same text.
+pub fn solve_part_1(input: &str) -> Result<u32> {
+    Ok(input.len() as u32)
+}
+
+#[allow(clippy::needless_range_loop)]
+/// New text.
+///
+/// `*2*`
+#[inline]
+pub fn solve_part_2(
+    input: &str,
+) -> Result<u32> {
+    /// Not this one.
+    fn inner() {}
+    Ok(0)
+}
+"#
+        );
+
+        // Refreshing with the same text changes nothing.
+        let again = replace_doc_comment(&refreshed, 2, "New text.\n\n`*2*`");
+        assert_eq!(again.unwrap(), refreshed);
+
+        // A function without a doc comment gets one, and a part that isn't
+        // unlocked says so.
+        let source = "#[cfg(test)]\npub fn solve_part_1() {}";
+        assert_eq!(
+            replace_doc_comment(source, 1, "").unwrap(),
+            "/// Not yet unlocked.\n#[cfg(test)]\npub fn solve_part_1() {}"
+        );
+
+        let e = replace_doc_comment(source, 2, "").unwrap_err();
+        assert_eq!(e.to_string(), "Missing part 2 function");
+        assert!(replace_doc_comment("pub fn solve_part_1(", 1, "").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Register new modules and solutions in the `lib.rs` and `mod.rs` files.
+use anyhow::Result;
+
+/// Add `pub mod name;` to the module declarations in a `lib.rs` or `mod.rs`,
+/// in sorted order. Attributes like `#[cfg(...)]` stay on the declaration
+/// after them, and nothing changes if the module is already declared. The
+/// first module is added at the end of the file.
+pub fn register_module(lib: &str, name: &str) -> Result<String> {
+    let lines = lib.lines().collect::<Vec<_>>();
+
+    // Find the existing module declarations, by name and line.
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| module_name(line).map(|name| (name, i)))
+        .collect::<Vec<_>>();
+    if modules.iter().any(|(module, _)| *module == name) {
+        return Ok(lib.to_string());
+    }
+
+    // Insert before the first module that sorts after this one, above its
+    // attributes, or after the last module.
+    let index = match modules.iter().find(|(module, _)| *module > name) {
+        Some((_, i)) => {
+            let mut i = *i;
+            while i > 0 && lines[i - 1].starts_with("#[") {
+                i -= 1;
+            }
+            i
+        }
+        None => modules.last().map_or(lines.len(), |(_, i)| i + 1),
+    };
+
+    let declaration = format!("pub mod {};", name);
+    let mut lines = lines;
+    lines.insert(index, &declaration);
+
+    Ok(with_newline(lines.join("\n")))
+}
+
+/// Add the solution for the day to the `SOLUTIONS` registry in `lib.rs`, in
+/// order of year and day. Nothing changes if it is already registered.
+pub fn register_solution(lib: &str, year: u32, day: u32) -> Result<String> {
+    let lines = lib.lines().collect::<Vec<_>>();
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static SOLUTIONS:"))
+        .ok_or_else(|| anyhow::anyhow!("Missing SOLUTIONS registry"))?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| line.starts_with("];"))
+        .map(|i| start + i)
+        .ok_or_else(|| anyhow::anyhow!("Missing end of SOLUTIONS registry"))?;
+
+    // The entries are zero padded, so they sort as strings.
+    let entry =
+        format!("    &{}::day{:02}::Day{:02},", year_module(year), day, day);
+    let entries = &lines[start + 1..end];
+    if entries.iter().any(|line| *line == entry) {
+        return Ok(lib.to_string());
+    }
+    let index = entries
+        .iter()
+        .position(|line| line.trim_start().starts_with('&') && **line > *entry)
+        .map_or(end, |i| start + 1 + i);
+
+    let mut lines = lines;
+    lines.insert(index, &entry);
+
+    Ok(with_newline(lines.join("\n")))
+}
+
+/// Get the name of the module for the puzzles of a year.
+pub fn year_module(year: u32) -> String {
+    format!("y{}", year)
+}
+
+/// Register the module for the day in `src/yYYYY/mod.rs`, the module for the
+/// year and the solution in `src/lib.rs`, and the kernel module for the day
+/// in `gpu/src/yYYYY/mod.rs` and its host module in `src/yYYYY/mod.rs` if
+/// there are any. The `mod.rs` for a new year is created.
+pub fn register(
+    working_dir: &std::path::Path,
+    year: u32,
+    day: u32,
+) -> Result<()> {
+    let module = format!("day{:02}", day);
+    let year_module = year_module(year);
+
+    let src_dir = working_dir.join("src");
+    let year_dir = src_dir.join(&year_module);
+    let has_host_module = year_dir.join(format!("gpu_{}.rs", module)).exists();
+    update(
+        &year_dir.join("mod.rs"),
+        &format!("//! The puzzles for Advent of Code {}.\n\n", year),
+        |lib| {
+            let lib = register_module(lib, &module)?;
+            if has_host_module {
+                return register_module(&lib, &format!("gpu_{}", module));
+            }
+            Ok(lib)
+        },
+    )?;
+    update(&src_dir.join("lib.rs"), "", |lib| {
+        let lib = register_module(lib, &year_module)?;
+        register_solution(&lib, year, day)
+    })?;
+
+    let gpu_src_dir = working_dir.join("gpu").join("src");
+    let kernel_dir = gpu_src_dir.join(&year_module);
+    if kernel_dir.join(format!("{}.rs", module)).exists() {
+        update(
+            &kernel_dir.join("mod.rs"),
+            &format!("//! The kernels for Advent of Code {}.\n\n", year),
+            |lib| register_module(lib, &module),
+        )?;
+        update(&gpu_src_dir.join("lib.rs"), "", |lib| {
+            register_module(lib, &year_module)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Update a file, only writing it if it changed. A file that doesn't exist
+/// yet starts from the header, unless the header is empty.
+fn update(
+    path: &std::path::Path,
+    header: &str,
+    f: impl FnOnce(&str) -> Result<String>,
+) -> Result<()> {
+    let existing = if !header.is_empty() && !path.exists() {
+        String::new()
+    } else {
+        std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!("Failed to read {}: {}", path.display(), e)
+        })?
+    };
+    let updated = f(if existing.is_empty() {
+        header
+    } else {
+        &existing
+    })?;
+    if updated != existing {
+        println!("Registering in {}", path.display());
+        std::fs::write(path, updated.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Get the name of the module a line declares, if it is a declaration.
+fn module_name(line: &str) -> Option<&str> {
+    line.strip_prefix("pub mod ")?.strip_suffix(';')
+}
+
+/// Make sure the file ends with a newline.
+fn with_newline(mut s: String) -> String {
+    s.push('\n');
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    const LIB: &str = r#"//! A library.
+
+#![deny(missing_docs)]
+
+pub mod add;
+#[cfg(feature = "cuda")]
+pub mod context;
+pub mod day01;
+pub mod day03;
+pub mod gpu_day08;
+#[cfg(feature = "cuda")]
+pub mod launch;
+
+pub use solution::Solution;
+
+/// Every registered solution, in order of year and day.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &y2022::day25::Day25,
+    &y2023::day01::Day01,
+    &y2023::day03::Day03,
+];
+"#;
+
+    #[test]
+    fn test_register_module() {
+        let lib = super::register_module(LIB, "day02").unwrap();
+        assert!(lib.contains("pub mod day01;\npub mod day02;\npub mod day03;"));
+        assert_eq!(super::register_module(&lib, "day02").unwrap(), lib);
+
+        // New modules go above the attributes of the next one.
+        let lib = super::register_module(LIB, "day14").unwrap();
+        assert!(
+            lib.contains("pub mod day03;\npub mod day14;\npub mod gpu_day08;")
+        );
+        let lib = super::register_module(LIB, "gpu_day14").unwrap();
+        assert!(lib.contains(
+            "pub mod gpu_day08;\npub mod gpu_day14;\n#[cfg(feature = \"cuda\")]\npub mod launch;"
+        ));
+        let lib = super::register_module(LIB, "answer").unwrap();
+        assert!(lib.contains(
+            "pub mod add;\npub mod answer;\n#[cfg(feature = \"cuda\")]\npub mod context;"
+        ));
+
+        // Or after the last one.
+        let lib = super::register_module(LIB, "solution").unwrap();
+        assert!(lib.contains("pub mod launch;\npub mod solution;\n\npub use"));
+
+        // A module with an attribute is already declared.
+        assert_eq!(super::register_module(LIB, "context").unwrap(), LIB);
+
+        // The first module goes at the end.
+        assert_eq!(
+            super::register_module("//! Empty.\n\n", "day01").unwrap(),
+            "//! Empty.\n\npub mod day01;\n"
+        );
+    }
+
+    #[test]
+    fn test_register_solution() {
+        let lib = super::register_solution(LIB, 2023, 2).unwrap();
+        assert!(lib.contains(
+            "    &y2023::day01::Day01,\n    &y2023::day02::Day02,\n    \
+             &y2023::day03::Day03,\n];"
+        ));
+        assert_eq!(super::register_solution(&lib, 2023, 2).unwrap(), lib);
+
+        let lib = super::register_solution(LIB, 2023, 14).unwrap();
+        assert!(lib.contains(
+            "    &y2023::day03::Day03,\n    &y2023::day14::Day14,\n];"
+        ));
+
+        // Years sort before days.
+        let lib = super::register_solution(LIB, 2022, 1).unwrap();
+        assert!(lib.contains(
+            "[\n    &y2022::day01::Day01,\n    &y2022::day25::Day25,\n"
+        ));
+
+        assert!(super::register_solution("pub mod day01;\n", 2023, 1).is_err());
+    }
+}
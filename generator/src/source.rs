@@ -0,0 +1,151 @@
+//! Where the puzzles and inputs come from.
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+/// A source of puzzles and inputs, by year and day.
+pub trait PuzzleSource {
+    /// Whether the puzzle for the day is unlocked yet.
+    fn day_unlocked(&self, year: u32, day: u32) -> Result<bool>;
+
+    /// Get the input for the day.
+    fn input(&self, year: u32, day: u32) -> Result<String>;
+
+    /// Get the puzzle for the day as markdown, with part two if it's
+    /// unlocked.
+    fn puzzle_markdown(&self, year: u32, day: u32) -> Result<String>;
+}
+
+/// The puzzles and inputs from Advent of Code, using the session cookie from
+/// the default locations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AocSource;
+
+impl AocSource {
+    /// Build a client for the day, saving the puzzle markdown to the path.
+    fn client(
+        &self,
+        year: u32,
+        day: u32,
+        puzzle_filename: &Path,
+    ) -> Result<aoc_client::AocClient> {
+        Ok(aoc_client::AocClient::builder()
+            .session_cookie_from_default_locations()?
+            .output_width(crate::OUTPUT_WIDTH)?
+            .overwrite_files(true)
+            .puzzle_filename(puzzle_filename)
+            .year(year as i32)?
+            .day(day)?
+            .build()?)
+    }
+
+    /// Get the path of a temporary file for the puzzle markdown.
+    fn temp_puzzle_filename(year: u32, day: u32) -> PathBuf {
+        std::env::temp_dir().join(format!("{}-day{:02}.md", year, day))
+    }
+}
+
+impl PuzzleSource for AocSource {
+    fn day_unlocked(&self, year: u32, day: u32) -> Result<bool> {
+        Ok(self
+            .client(year, day, &Self::temp_puzzle_filename(year, day))?
+            .day_unlocked())
+    }
+
+    fn input(&self, year: u32, day: u32) -> Result<String> {
+        Ok(self
+            .client(year, day, &Self::temp_puzzle_filename(year, day))?
+            .get_input()?)
+    }
+
+    fn puzzle_markdown(&self, year: u32, day: u32) -> Result<String> {
+        // The client can only save the markdown to a file, so read it back
+        // and clean up.
+        let puzzle_filename = Self::temp_puzzle_filename(year, day);
+        self.client(year, day, &puzzle_filename)?
+            .save_puzzle_markdown()?;
+        let puzzle_markdown = std::fs::read_to_string(&puzzle_filename)?;
+        std::fs::remove_file(&puzzle_filename)?;
+
+        Ok(puzzle_markdown)
+    }
+}
+
+impl crate::Submitter for AocSource {
+    fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String> {
+        Ok(self
+            .client(year, day, &Self::temp_puzzle_filename(year, day))?
+            .submit_answer_html(part as i64, answer)?)
+    }
+}
+
+/// Puzzles and inputs from files on disk, named `YYYY/dayNN.md` and
+/// `YYYY/dayNN.txt`. A day is unlocked if its puzzle file exists.
+///
+/// This is how the generator works offline from the cache in the repo, and
+/// how the tests stand in for Advent of Code with fixtures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSource {
+    /// The directory with the puzzle markdown files.
+    pub puzzle_dir: PathBuf,
+    /// The directory with the input files.
+    pub input_dir: PathBuf,
+}
+
+impl FileSource {
+    /// Create a source with the puzzles and inputs in the same directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        FileSource {
+            puzzle_dir: dir.clone(),
+            input_dir: dir,
+        }
+    }
+
+    /// Create a source from the puzzles and inputs cached in the repo.
+    pub fn cache(working_dir: &Path) -> Self {
+        FileSource {
+            puzzle_dir: working_dir.join(crate::PUZZLE_DIR),
+            input_dir: working_dir.join(crate::INPUT_DIR),
+        }
+    }
+
+    /// Get the path of the puzzle markdown for the day.
+    pub fn puzzle_path(&self, year: u32, day: u32) -> PathBuf {
+        self.puzzle_dir
+            .join(year.to_string())
+            .join(format!("day{:02}.md", day))
+    }
+}
+
+/// Read a file, with the path in the error.
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        anyhow::anyhow!("Failed to read {}: {}", path.display(), e)
+    })
+}
+
+impl PuzzleSource for FileSource {
+    fn day_unlocked(&self, year: u32, day: u32) -> Result<bool> {
+        Ok(self.puzzle_path(year, day).exists())
+    }
+
+    fn input(&self, year: u32, day: u32) -> Result<String> {
+        read(
+            &self
+                .input_dir
+                .join(year.to_string())
+                .join(format!("day{:02}.txt", day)),
+        )
+    }
+
+    fn puzzle_markdown(&self, year: u32, day: u32) -> Result<String> {
+        read(&self.puzzle_path(year, day))
+    }
+}
@@ -0,0 +1,494 @@
+//! Submit answers, and keep a history of every attempt so a known wrong answer
+//! is never submitted twice.
+use std::{
+    fmt,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+
+/// The name of the history of submissions in the input directory for each
+/// year.
+pub const HISTORY_FILE: &str = "submissions.txt";
+
+/// Somewhere to submit answers to.
+pub trait Submitter {
+    /// Post the answer for the part, and get the page that comes back.
+    fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String>;
+}
+
+/// A local server standing in for Advent of Code, at an `http://host:port`
+/// address. It gets the same form posted to the same path, but over plain
+/// HTTP and without a session cookie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalServer {
+    /// The `host:port` of the server.
+    pub address: String,
+}
+
+impl LocalServer {
+    /// Create a submitter for the server at the URL.
+    pub fn new(url: &str) -> Result<Self> {
+        let address = url
+            .strip_prefix("http://")
+            .ok_or_else(|| anyhow::anyhow!("Expected an http:// URL: {}", url))?
+            .trim_end_matches('/');
+
+        Ok(LocalServer {
+            address: address.to_string(),
+        })
+    }
+}
+
+impl Submitter for LocalServer {
+    fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String> {
+        let body = format!("level={}&answer={}", part, answer);
+        let request = format!(
+            "POST /{}/day/{}/answer HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Content-Type: application/x-www-form-urlencoded\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            year,
+            day,
+            self.address,
+            body.len(),
+            body
+        );
+
+        let mut stream =
+            std::net::TcpStream::connect(&self.address).map_err(|e| {
+                anyhow::anyhow!("Failed to connect to {}: {}", self.address, e)
+            })?;
+        stream.write_all(request.as_bytes())?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| anyhow::anyhow!("Invalid response: {}", response))?;
+        let status = head.lines().next().unwrap_or("");
+        if status.split_whitespace().nth(1) != Some("200") {
+            anyhow::bail!("Unexpected response: {}", status);
+        }
+
+        Ok(body.to_string())
+    }
+}
+
+/// What Advent of Code said about an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The answer is right.
+    Correct,
+    /// The answer is wrong, and higher than the right one.
+    TooHigh,
+    /// The answer is wrong, and lower than the right one.
+    TooLow,
+    /// The answer is wrong.
+    Incorrect,
+    /// An answer was submitted too recently, so this one wasn't checked.
+    Wait(u64),
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Parse the page that comes back from submitting an answer.
+    pub fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Ok(Outcome::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Ok(Outcome::TooLow)
+            } else {
+                Ok(Outcome::Incorrect)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            let re =
+                regex::Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")?;
+            let captures = re.captures(page);
+            let time = |i| {
+                captures
+                    .as_ref()
+                    .and_then(|captures| captures.get(i))
+                    .map_or(Ok(0), |time| time.as_str().parse::<u64>())
+            };
+            Ok(Outcome::Wait(time(1)? * 60 + time(2)?))
+        } else if page.contains("You don't seem to be solving the right level")
+        {
+            Ok(Outcome::WrongLevel)
+        } else {
+            anyhow::bail!("Unrecognized response: {}", page)
+        }
+    }
+
+    /// Whether the answer was checked and found wrong, so it should never be
+    /// submitted again.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::Wait(seconds) => write!(f, "wait-{}s", seconds),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "incorrect" => Ok(Outcome::Incorrect),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            _ => {
+                let seconds = s
+                    .strip_prefix("wait-")
+                    .and_then(|s| s.strip_suffix('s'))
+                    .ok_or_else(|| anyhow::anyhow!("invalid outcome: {}", s))?;
+                Ok(Outcome::Wait(seconds.parse()?))
+            }
+        }
+    }
+}
+
+/// An answer that was submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// When it was submitted, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The day of the puzzle.
+    pub day: u32,
+    /// The part of the puzzle.
+    pub part: u32,
+    /// What Advent of Code said about it.
+    pub outcome: Outcome,
+    /// The answer.
+    pub answer: String,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.timestamp, self.day, self.part, self.outcome, self.answer
+        )
+    }
+}
+
+/// Every answer submitted for a year.
+///
+/// Each line of the file is `timestamp day part outcome answer`, blank lines
+/// and lines starting with `#` are skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    /// The attempts, oldest first.
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Parse the history.
+    pub fn parse(s: &str) -> Result<Self> {
+        let attempts = s
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                parse_attempt(line).map_err(|e| {
+                    anyhow::anyhow!("invalid attempt on line {}: {}", i + 1, e)
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(History { attempts })
+    }
+
+    /// Load the history for the year from its directory in the input
+    /// directory. A year without a history has no attempts yet.
+    pub fn load(input_dir: &Path, year: u32) -> Result<Self> {
+        let path = history_path(input_dir, year);
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let s = std::fs::read_to_string(&path).map_err(|e| {
+            anyhow::anyhow!("Failed to read {}: {}", path.display(), e)
+        })?;
+
+        History::parse(&s)
+    }
+
+    /// Check the answer against the earlier attempts for the part, and fail
+    /// if it is already known to be wrong, or the part is already solved.
+    ///
+    /// An answer that was too high rules out every higher answer, and one
+    /// that was too low every lower answer.
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Result<()> {
+        let value = answer.parse::<i128>().ok();
+        for attempt in self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part)
+        {
+            let earlier = attempt.answer.parse::<i128>().ok();
+            let ruled_out = match (attempt.outcome, value, earlier) {
+                (Outcome::Correct, _, _) => anyhow::bail!(
+                    "Day {} part {} is already solved with {}",
+                    day,
+                    part,
+                    attempt.answer
+                ),
+                (outcome, _, _)
+                    if outcome.is_wrong() && attempt.answer == answer =>
+                {
+                    true
+                }
+                (Outcome::TooHigh, Some(value), Some(earlier)) => {
+                    value >= earlier
+                }
+                (Outcome::TooLow, Some(value), Some(earlier)) => {
+                    value <= earlier
+                }
+                _ => false,
+            };
+            if ruled_out {
+                anyhow::bail!(
+                    "{} is wrong for day {} part {}, {} was {}",
+                    answer,
+                    day,
+                    part,
+                    attempt.answer,
+                    attempt.outcome
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a `timestamp day part outcome answer` line.
+fn parse_attempt(line: &str) -> Result<Attempt> {
+    let mut fields = line.splitn(5, char::is_whitespace);
+    let mut next = |name: &str| {
+        fields
+            .next()
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("missing {}", name))
+    };
+
+    Ok(Attempt {
+        timestamp: next("timestamp")?.parse()?,
+        day: next("day")?.parse()?,
+        part: next("part")?.parse()?,
+        outcome: next("outcome")?.parse()?,
+        answer: next("answer")?.to_string(),
+    })
+}
+
+/// Get the path of the history for the year.
+pub fn history_path(input_dir: &Path, year: u32) -> PathBuf {
+    input_dir.join(year.to_string()).join(HISTORY_FILE)
+}
+
+/// Submit the answer for the part, unless the history shows it is wrong or
+/// the part is already solved, and record the attempt in the history for the
+/// year.
+pub fn submit(
+    submitter: &dyn Submitter,
+    working_dir: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Outcome> {
+    let answer = answer.trim();
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        anyhow::bail!("Invalid answer: {:?}", answer);
+    }
+
+    let input_dir = working_dir.join(crate::INPUT_DIR);
+    History::load(&input_dir, year)?.check(day, part, answer)?;
+
+    let page = submitter.submit(year, day, part, answer)?;
+    let outcome = Outcome::parse(&page)?;
+
+    // Record the attempt, starting the history for the year if needed.
+    let attempt = Attempt {
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        day,
+        part,
+        outcome,
+        answer: answer.to_string(),
+    };
+    let path = history_path(&input_dir, year);
+    std::fs::create_dir_all(input_dir.join(year.to_string()))?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", attempt)?;
+
+    Ok(outcome)
+}
+
+/// Get the answer for the part from the runner, with the input in the repo.
+pub fn runner_answer(
+    working_dir: &Path,
+    year: u32,
+    day: u32,
+    part: u32,
+) -> Result<String> {
+    let output = std::process::Command::new("cargo")
+        .args(["run", "--release", "--quiet", "--", "--year"])
+        .arg(year.to_string())
+        .arg(day.to_string())
+        .arg(part.to_string())
+        .current_dir(working_dir)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "The runner failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    // The answer is the first line, any warnings go to stderr.
+    String::from_utf8(output.stdout)?
+        .lines()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("The runner printed no answer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::Outcome;
+
+    #[test]
+    fn test_parse_outcome() {
+        let page = |name: &str| {
+            std::fs::read_to_string(
+                std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("tests")
+                    .join("fixtures")
+                    .join("submit")
+                    .join(name),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            Outcome::parse(&page("correct.html")).unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse(&page("too_high.html")).unwrap(),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            Outcome::parse(&page("too_low.html")).unwrap(),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::parse(&page("incorrect.html")).unwrap(),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            Outcome::parse(&page("wait.html")).unwrap(),
+            Outcome::Wait(62)
+        );
+        assert_eq!(
+            Outcome::parse(&page("wrong_level.html")).unwrap(),
+            Outcome::WrongLevel
+        );
+        assert!(Outcome::parse("<main></main>").is_err());
+
+        // The outcomes round trip through the history.
+        for outcome in
+            [Outcome::TooHigh, Outcome::Wait(62), Outcome::WrongLevel]
+        {
+            assert_eq!(
+                outcome.to_string().parse::<Outcome>().unwrap(),
+                outcome
+            );
+        }
+        assert!("wait".parse::<Outcome>().is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let history = super::History::parse(
+            r#"# Submissions.
+1701993600 8 1 too-high 500
+1701993660 8 1 wait-30s 400
+1701993720 8 1 too-low 100
+1701993780 8 1 incorrect 250
+1701993840 8 1 correct 300
+1701993900 8 2 incorrect abc
+"#,
+        )
+        .unwrap();
+        assert_eq!(history.attempts.len(), 6);
+        assert_eq!(history.attempts[1].outcome, Outcome::Wait(30));
+
+        // A solved part is never submitted again.
+        let e = history.check(8, 1, "300").unwrap_err();
+        assert_eq!(e.to_string(), "Day 8 part 1 is already solved with 300");
+
+        // Nor is a wrong answer, or one the bounds rule out.
+        let e = history.check(8, 2, "abc").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "abc is wrong for day 8 part 2, abc was incorrect"
+        );
+        let history = super::History {
+            attempts: history.attempts[..4].to_vec(),
+        };
+        for answer in ["250", "500", "501", "100", "-1"] {
+            assert!(history.check(8, 1, answer).is_err(), "{}", answer);
+        }
+        for answer in ["400", "499", "101"] {
+            assert!(history.check(8, 1, answer).is_ok(), "{}", answer);
+        }
+        assert!(history.check(9, 1, "500").is_ok());
+
+        assert!(super::History::parse("1701993600 8 1 too-high").is_err());
+        assert!(super::History::parse("1701993600 8 1 maybe 5").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Fill in the templates for the files the generator writes, from the
+//! `templates/` directory of the repo.
+//!
+//! A template is the file as it should be written, with `{{name}}`
+//! placeholders for the parts that change with the puzzle. A placeholder
+//! alone on a line with nothing to fill in removes the line.
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::example::{self, Example};
+
+/// The directory, relative to the repo, the templates are in.
+pub const TEMPLATE_DIR: &str = "templates";
+
+/// The templates of a repo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Templates {
+    /// The directory the templates are in.
+    dir: PathBuf,
+}
+
+impl Templates {
+    /// Get the templates in the repo.
+    pub fn new(working_dir: &Path) -> Self {
+        Templates {
+            dir: working_dir.join(TEMPLATE_DIR),
+        }
+    }
+
+    /// Fill in the template with the name, failing if it has a placeholder
+    /// without a value.
+    pub fn render(
+        &self,
+        name: &str,
+        values: &[(&str, &str)],
+    ) -> Result<String> {
+        let path = self.dir.join(name);
+        let template = std::fs::read_to_string(&path).map_err(|e| {
+            anyhow::anyhow!("Failed to read template {}: {}", path.display(), e)
+        })?;
+
+        render(&template, values)
+            .map_err(|e| anyhow::anyhow!("{} in {}", e, path.display()))
+    }
+
+    /// The solve function for a part that isn't solved yet.
+    pub fn solve_fn(&self, part: u32, answer_type: &str) -> Result<String> {
+        let part = part.to_string();
+        let rendered = self.render(
+            "solve_part.rs",
+            &[("part", &part), ("answer_type", answer_type)],
+        )?;
+
+        Ok(rendered.trim_end().to_string())
+    }
+
+    /// The test for a part, with the example if there is one and the input for
+    /// the day, which is skipped if the input can't be decrypted. It is
+    /// ignored until the part is solved and the answer filled in.
+    pub fn test_fn(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        example: Option<&Example>,
+    ) -> Result<String> {
+        let example = example.map_or_else(String::new, |example| {
+            example::assertion(part, example)
+        });
+        let part = part.to_string();
+        let rendered = self.render(
+            "test_part.rs",
+            &[
+                ("year", &year.to_string()),
+                ("day", &day.to_string()),
+                ("part", &part),
+                ("example", &example),
+            ],
+        )?;
+
+        Ok(rendered.trim_end().to_string())
+    }
+}
+
+/// Fill in the placeholders in the template with the values.
+fn render(template: &str, values: &[(&str, &str)]) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    for line in template.split_inclusive('\n') {
+        // Fill in the line a placeholder at a time, so nothing in the values
+        // is taken for a placeholder.
+        let mut filled = String::new();
+        let mut rest = line;
+        while let Some(start) = rest.find("{{") {
+            let end = rest[start..].find("}}").ok_or_else(|| {
+                anyhow::anyhow!("Unclosed placeholder: {}", line.trim_end())
+            })?;
+            let name = &rest[start + 2..start + end];
+            let value = values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Unknown placeholder {{{{{}}}}}", name)
+                })?;
+            filled.push_str(&rest[..start]);
+            filled.push_str(value);
+            rest = &rest[start + end + 2..];
+        }
+        filled.push_str(rest);
+
+        let placeholder_only = line.trim().starts_with("{{")
+            && line.trim().ends_with("}}")
+            && line.trim().matches("{{").count() == 1;
+        if placeholder_only && filled.trim().is_empty() {
+            continue;
+        }
+        rendered.push_str(&filled);
+    }
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    /// The templates of this repo.
+    fn templates() -> super::Templates {
+        super::Templates::new(
+            Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_render() {
+        let values = [("day", "8"), ("title", "{{day}}"), ("empty", "")];
+        assert_eq!(
+            super::render("Day {{day}}: {{title}}\n", &values).unwrap(),
+            "Day 8: {{day}}\n"
+        );
+
+        // A line with only a placeholder goes away if there's nothing in it.
+        assert_eq!(
+            super::render("a\n    {{empty}}\nb {{empty}}\n", &values).unwrap(),
+            "a\nb \n"
+        );
+
+        let e = super::render("{{day}} {{month}}", &values).unwrap_err();
+        assert_eq!(e.to_string(), "Unknown placeholder {{month}}");
+        assert!(super::render("{{day", &values).is_err());
+    }
+
+    #[test]
+    fn test_test_fn() {
+        let example = crate::Example {
+            input: "a \"# b".to_string(),
+            answer: "15726453850399".to_string(),
+        };
+        assert_eq!(
+            templates().test_fn(2023, 8, 2, Some(&example)).unwrap(),
+            r####"    #[test]
+    #[ignore = "not yet solved"]
+    fn test_solve_part_2() {
+        let input = r##"a "# b"##;
+        assert_eq!(super::solve_part_2(input).unwrap(), 15726453850399);
+
+        // Load the file.
+        let input = crate::test_input!(2023, 8);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 0);
+    }"####
+        );
+        assert_eq!(
+            templates().test_fn(2023, 8, 1, None).unwrap(),
+            r#"    #[test]
+    #[ignore = "not yet solved"]
+    fn test_solve_part_1() {
+        // Load the file.
+        let input = crate::test_input!(2023, 8);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 0);
+    }"#
+        );
+        assert_eq!(
+            templates().solve_fn(1, "i64").unwrap(),
+            "pub fn solve_part_1(_input: &str) -> Result<i64> {\n    \
+             Err(crate::Unimplemented.into())\n}"
+        );
+
+        assert!(super::Templates::new(Path::new("/nonexistent"))
+            .solve_fn(1, "u32")
+            .is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Advent of Code using CUDA and Rust. This is the GPU specific code.
+
+#![deny(missing_docs)]
+
+extern crate alloc;
+
+pub mod add;
+#[cfg(not(target_os = "cuda"))]
+pub mod emulator;
+pub mod y2023;
@@ -0,0 +1,3 @@
+//! The kernels for Advent of Code 2023.
+
+pub mod day08;
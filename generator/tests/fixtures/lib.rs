@@ -0,0 +1,21 @@
+//! Advent of Code using CUDA and Rust.
+
+#![deny(missing_docs)]
+
+pub mod add;
+pub mod answer;
+#[cfg(feature = "cuda")]
+pub mod context;
+#[cfg(feature = "cuda")]
+pub mod launch;
+pub mod solution;
+pub mod y2023;
+
+pub use answer::Answer;
+pub use solution::Solution;
+
+/// Every registered solution, in order of year and day.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &y2023::day01::Day01,
+    &y2023::day13::Day13,
+];
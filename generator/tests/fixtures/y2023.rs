@@ -0,0 +1,5 @@
+//! The puzzles for Advent of Code 2023.
+
+pub mod day01;
+pub mod day13;
+pub mod gpu_day08;
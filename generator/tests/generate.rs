@@ -0,0 +1,435 @@
+//! Generate days from fixtures standing in for Advent of Code.
+use std::path::Path;
+
+use aoc_input::Key;
+use generator::{FileSource, PuzzleSource, TEMPLATE_DIR};
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+/// The `src/lib.rs` of the fake repo.
+const LIB: &str = include_str!("fixtures/lib.rs");
+
+/// The `src/y2023/mod.rs` of the fake repo.
+const YEAR_MOD: &str = include_str!("fixtures/y2023.rs");
+
+/// The `gpu/src/lib.rs` of the fake repo.
+const GPU_LIB: &str = include_str!("fixtures/gpu_lib.rs");
+
+/// The `gpu/src/y2023/mod.rs` of the fake repo.
+const GPU_YEAR_MOD: &str = include_str!("fixtures/gpu_y2023.rs");
+
+/// A fake repo to generate into, in a temporary directory.
+struct Repo {
+    temp: TempDir,
+}
+
+impl Repo {
+    fn new() -> Self {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("src").join("y2023")).unwrap();
+        std::fs::create_dir_all(dir.join("input")).unwrap();
+        std::fs::write(dir.join("src").join("lib.rs"), LIB).unwrap();
+        std::fs::write(dir.join("src").join("y2023").join("mod.rs"), YEAR_MOD)
+            .unwrap();
+
+        // Start from the templates of this repo.
+        let templates = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join(TEMPLATE_DIR);
+        std::fs::create_dir_all(dir.join(TEMPLATE_DIR)).unwrap();
+        for entry in std::fs::read_dir(templates).unwrap() {
+            let path = entry.unwrap().path();
+            std::fs::copy(
+                &path,
+                dir.join(TEMPLATE_DIR).join(path.file_name().unwrap()),
+            )
+            .unwrap();
+        }
+
+        Repo { temp }
+    }
+
+    /// The root of the repo.
+    fn dir(&self) -> &Path {
+        self.temp.path()
+    }
+
+    /// Add the GPU crate, with the kernels for 2023.
+    fn with_gpu(self) -> Self {
+        let gpu_src_dir = self.dir().join("gpu").join("src");
+        std::fs::create_dir_all(gpu_src_dir.join("y2023")).unwrap();
+        std::fs::write(gpu_src_dir.join("lib.rs"), GPU_LIB).unwrap();
+        std::fs::write(gpu_src_dir.join("y2023").join("mod.rs"), GPU_YEAR_MOD)
+            .unwrap();
+        self
+    }
+
+    fn read(&self, path: &str) -> String {
+        std::fs::read_to_string(self.dir().join(path)).unwrap()
+    }
+
+    /// Decrypt the input with the key for the repo.
+    fn read_input(&self, path: &str) -> String {
+        let key = Key::load(self.dir()).unwrap().unwrap();
+        let encrypted =
+            std::fs::read(aoc_input::encrypted_path(&self.dir().join(path)))
+                .unwrap();
+        key.decrypt(&encrypted).unwrap()
+    }
+}
+
+/// The fixtures with only part one of the puzzle unlocked, or with both.
+fn fixtures(name: &str) -> FileSource {
+    FileSource::new(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(name),
+    )
+}
+
+#[test]
+fn test_new_day() {
+    let repo = Repo::new();
+    generator::generate(&fixtures("part1"), repo.dir(), 2023, 14).unwrap();
+
+    // The input and puzzle are cached, with the input encrypted.
+    assert_eq!(
+        repo.read_input("input/2023/day14.txt"),
+        "O....#....\nO.OO#....#\n.....##...\n"
+    );
+    assert!(!repo.dir().join("input/2023/day14.txt").exists());
+    assert_eq!(
+        repo.read("puzzles/2023/day14.md"),
+        fixtures("part1").puzzle_markdown(2023, 14).unwrap()
+    );
+
+    let template = repo.read("src/y2023/day14.rs");
+    assert!(template.starts_with("//!  Day 14: Parabolic Reflector Dish\n"));
+    assert!(template.contains(
+        "/// You reach the place where all of the mirrors were pointing: a \
+         massive parabolic reflector dish \
+         (<https://en.wikipedia.org/wiki/Parabolic_reflector>)"
+    ));
+    assert!(template.contains(
+        "/// ```ignore\n/// O....#....\n/// O.OO#....#\n/// .....##...\n/// ```\n"
+    ));
+    assert!(template.contains(
+        "Afterward, *what is the total load on the north support beams?*\n\
+         pub fn solve_part_1(_input: &str)"
+    ));
+    assert!(template
+        .contains("/// Not yet unlocked.\npub fn solve_part_2(_input: &str)"));
+    assert!(template.contains("crate::test_input!(2023, 14)"));
+    assert!(template.contains("pub struct Day14;"));
+    assert!(template.contains("    fn year(&self) -> u32 {\n        2023\n"));
+    assert!(template.contains("\"Parabolic Reflector Dish\""));
+
+    // Part one is tested with the example, part two isn't unlocked yet.
+    assert!(template.contains(
+        r##"    fn test_solve_part_1() {
+        let input = r#"O....#....
+O.OO#....#
+.....##..."#;
+        assert_eq!(super::solve_part_1(input).unwrap(), 136);
+
+        // Load the file.
+        let input = crate::test_input!(2023, 14);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 0);
+    }"##
+    ));
+    assert!(template.contains(
+        r#"    fn test_solve_part_2() {
+        // Load the file.
+"#
+    ));
+
+    // The day is registered, after the days before it.
+    assert!(repo
+        .read("src/y2023/mod.rs")
+        .contains("pub mod day13;\npub mod day14;\npub mod gpu_day08;"));
+    let lib = repo.read("src/lib.rs");
+    assert_eq!(lib.matches("pub mod y2023;").count(), 1);
+    assert!(lib
+        .contains("    &y2023::day13::Day13,\n    &y2023::day14::Day14,\n];"));
+}
+
+#[test]
+fn test_new_year() {
+    let repo = Repo::new();
+
+    // Stand the 2023 puzzle in for 2022, in the cache of the repo.
+    for (dir, file) in [("puzzles", "day14.md"), ("input", "day14.txt")] {
+        let year_dir = repo.dir().join(dir).join("2022");
+        std::fs::create_dir_all(&year_dir).unwrap();
+        std::fs::write(
+            year_dir.join(file),
+            fixtures("part1").puzzle_markdown(2023, 14).unwrap(),
+        )
+        .unwrap();
+    }
+    generator::generate(&FileSource::cache(repo.dir()), repo.dir(), 2022, 14)
+        .unwrap();
+
+    // The year gets its own module, before the later years.
+    assert!(repo
+        .read("src/y2022/day14.rs")
+        .contains("crate::test_input!(2022, 14)"));
+    assert_eq!(
+        repo.read("src/y2022/mod.rs"),
+        "//! The puzzles for Advent of Code 2022.\n\npub mod day14;\n"
+    );
+    let lib = repo.read("src/lib.rs");
+    assert!(lib.contains("pub mod solution;\npub mod y2022;\npub mod y2023;"));
+    assert!(lib
+        .contains("[\n    &y2022::day14::Day14,\n    &y2023::day01::Day01,\n"));
+    assert_eq!(repo.read("src/y2023/mod.rs"), YEAR_MOD);
+}
+
+#[test]
+fn test_register_kernel() {
+    let repo = Repo::new().with_gpu();
+    let kernel_dir = repo.dir().join("gpu").join("src").join("y2023");
+    std::fs::write(kernel_dir.join("day14.rs"), "").unwrap();
+
+    generator::generate(&fixtures("part1"), repo.dir(), 2023, 14).unwrap();
+    generator::generate(&fixtures("part2"), repo.dir(), 2023, 14).unwrap();
+
+    // The kernel module is registered once, and the year is already.
+    assert_eq!(
+        repo.read("gpu/src/y2023/mod.rs"),
+        GPU_YEAR_MOD
+            .replace("pub mod day08;\n", "pub mod day08;\npub mod day14;\n")
+    );
+    assert_eq!(repo.read("gpu/src/lib.rs"), GPU_LIB);
+    assert_eq!(repo.read("src/lib.rs").matches("day14").count(), 1);
+    assert_eq!(repo.read("src/y2023/mod.rs").matches("day14").count(), 1);
+}
+
+#[test]
+fn test_refresh_part_two() {
+    let repo = Repo::new();
+    generator::generate(&fixtures("part1"), repo.dir(), 2023, 14).unwrap();
+
+    // Solve part one, then refresh once part two is unlocked.
+    let path = repo.dir().join("src").join("y2023").join("day14.rs");
+    let solved = repo.read("src/y2023/day14.rs").replace(
+        "pub fn solve_part_1(_input: &str) -> Result<u32> {\n    \
+         Err(crate::Unimplemented.into())",
+        "#[inline]\npub fn solve_part_1(\n    _input: &str,\n) -> Result<u32> \
+         {\n    Ok(136)",
+    );
+    std::fs::write(&path, &solved).unwrap();
+    let key = Key::load(repo.dir()).unwrap().unwrap();
+    std::fs::write(
+        repo.dir().join("input").join("2023").join("day14.txt.enc"),
+        key.encrypt("mine\n").unwrap(),
+    )
+    .unwrap();
+    generator::generate(&fixtures("part2"), repo.dir(), 2023, 14).unwrap();
+
+    // The code and the cached input are kept.
+    let template = repo.read("src/y2023/day14.rs");
+    assert!(template.contains(
+        "on the north support beams?*\n#[inline]\npub fn solve_part_1(\n"
+    ));
+    assert!(template.contains("    Ok(136)\n"));
+    assert_eq!(repo.read_input("input/2023/day14.txt"), "mine\n");
+    assert!(repo
+        .read("puzzles/2023/day14.md")
+        .contains("--- Part Two ---"));
+
+    assert!(!template.contains("Not yet unlocked."));
+    assert!(!template.contains("Your puzzle answer was"));
+    assert!(!template.contains("Although it hasn't changed"));
+    assert!(template.contains(
+        "/// The parabolic reflector dish deforms, but not in a way that \
+         focuses the beam.\n\
+         ///\n\
+         /// In the above example, after `1000000000` cycles, the total load \
+         on the north support beams is `*64*`.\n\
+         ///\n\
+         /// Run the spin cycle for `1000000000` cycles. Afterward, *what is \
+         the total load on the north support beams?*\n\
+         pub fn solve_part_2(_input: &str)"
+    ));
+    assert_eq!(template.matches("pub fn solve_part_").count(), 2);
+    assert_eq!(template.matches("//!  Day 14").count(), 1);
+
+    // Part two gets the example from part one, with its own answer.
+    assert!(template.contains(
+        r##"    fn test_solve_part_2() {
+        let input = r#"O....#....
+O.OO#....#
+.....##..."#;
+        assert_eq!(super::solve_part_2(input).unwrap(), 64);
+"##
+    ));
+    assert_eq!(template.matches("136);").count(), 1);
+}
+
+#[test]
+fn test_edit_template() {
+    let repo = Repo::new();
+
+    // Add a benchmark to every new day, and look at the answer type.
+    let path = repo.dir().join(TEMPLATE_DIR).join("day.rs");
+    let template = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(
+        &path,
+        format!(
+            "{}\n/// Day {{{{day}}}} answers {{{{part_one_type}}}} for \
+             {{{{title}}}}.\n#[cfg(test)]\nmod benches {{}}\n",
+            template
+        ),
+    )
+    .unwrap();
+    generator::generate(&fixtures("part1"), repo.dir(), 2023, 14).unwrap();
+    assert!(repo.read("src/y2023/day14.rs").ends_with(
+        "}\n\n/// Day 14 answers u32 for Parabolic Reflector Dish.\n\
+         #[cfg(test)]\nmod benches {}\n"
+    ));
+
+    // A typo in a placeholder writes nothing.
+    let day_path = repo.dir().join("src").join("y2023").join("day14.rs");
+    std::fs::remove_file(&day_path).unwrap();
+    std::fs::write(&path, "//! {{tilte}}\n").unwrap();
+    let e = generator::generate(&fixtures("part1"), repo.dir(), 2023, 14)
+        .unwrap_err();
+    assert!(e
+        .to_string()
+        .starts_with("Unknown placeholder {{tilte}} in "));
+    assert!(!day_path.exists());
+}
+
+#[test]
+fn test_offline() {
+    let repo = Repo::new();
+    generator::generate(&fixtures("part2"), repo.dir(), 2023, 14).unwrap();
+    let generated = repo.read("src/y2023/day14.rs");
+
+    // Regenerating from the cache in the repo changes nothing.
+    std::fs::remove_file(repo.dir().join("src").join("y2023").join("day14.rs"))
+        .unwrap();
+    generator::generate(&FileSource::cache(repo.dir()), repo.dir(), 2023, 14)
+        .unwrap();
+    assert_eq!(repo.read("src/y2023/day14.rs"), generated);
+
+    // So does refreshing the day that is already there.
+    generator::generate(&FileSource::cache(repo.dir()), repo.dir(), 2023, 14)
+        .unwrap();
+    assert_eq!(repo.read("src/y2023/day14.rs"), generated);
+}
+
+#[test]
+fn test_locked_day() {
+    let repo = Repo::new();
+    let e = generator::generate(&fixtures("part2"), repo.dir(), 2023, 15)
+        .unwrap_err();
+    assert_eq!(e.to_string(), "2023 day 15 is not unlocked");
+
+    // Nothing is written for a locked day.
+    assert!(!repo
+        .dir()
+        .join("src")
+        .join("y2023")
+        .join("day15.rs")
+        .exists());
+    assert!(!repo
+        .dir()
+        .join("input")
+        .join("2023")
+        .join("day15.txt")
+        .exists());
+    assert!(!repo
+        .dir()
+        .join("puzzles")
+        .join("2023")
+        .join("day15.md")
+        .exists());
+    assert_eq!(repo.read("src/lib.rs"), LIB);
+    assert_eq!(repo.read("src/y2023/mod.rs"), YEAR_MOD);
+}
+
+#[test]
+fn test_generate_gpu() {
+    let repo = Repo::new().with_gpu();
+
+    // The puzzle has to be cached first, for the title.
+    assert!(generator::generate_gpu(repo.dir(), 2023, 14).is_err());
+    generator::generate(&fixtures("part1"), repo.dir(), 2023, 14).unwrap();
+    generator::generate_gpu(repo.dir(), 2023, 14).unwrap();
+
+    let kernel = repo.read("gpu/src/y2023/day14.rs");
+    assert!(kernel.starts_with(
+        "//!  CUDA Kernel for day 14: Parabolic Reflector Dish\n"
+    ));
+    assert!(kernel.contains("#[kernel]\n"));
+    assert!(kernel.contains(
+        "pub unsafe fn day14_kernel(input: &[u32], output: *mut u32) {"
+    ));
+
+    let host = repo.read("src/y2023/gpu_day14.rs");
+    assert!(host.contains("Launch::new(\"day14_kernel\")"));
+    assert!(host.contains("gpu::y2023::day14::day14_kernel(input, output_ptr)"));
+    assert!(host.contains("crate::test_input!(2023, 14)"));
+
+    // Both are registered, next to the other modules.
+    assert!(repo
+        .read("gpu/src/y2023/mod.rs")
+        .contains("pub mod day08;\npub mod day14;\n"));
+    assert!(repo
+        .read("src/y2023/mod.rs")
+        .ends_with("pub mod gpu_day08;\npub mod gpu_day14;\n"));
+
+    // Scaffolding again keeps the work done on them.
+    let kernel_path = repo.dir().join("gpu/src/y2023/day14.rs");
+    std::fs::write(&kernel_path, "// Mine.\n").unwrap();
+    generator::generate_gpu(repo.dir(), 2023, 14).unwrap();
+    assert_eq!(repo.read("gpu/src/y2023/day14.rs"), "// Mine.\n");
+    assert_eq!(repo.read("src/y2023/gpu_day14.rs"), host);
+}
+
+#[test]
+fn test_encrypt_inputs() {
+    let repo = Repo::new();
+    for (path, contents) in [
+        ("input/2022/day02.txt", "2022\n"),
+        ("input/2023/day01.txt", "mine\n"),
+        ("input/2023/day01-alice.txt", "alice's\n"),
+        ("input/2023/answers.txt", "1 1 day01.txt 1\n"),
+        ("input/2023/submissions.txt", "1 1 1 correct 1\n"),
+    ] {
+        let path = repo.dir().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    // Only the inputs for the year are encrypted, and the plain ones go.
+    let encrypted = generator::encrypt_inputs(repo.dir(), Some(2023)).unwrap();
+    assert_eq!(
+        encrypted,
+        vec![
+            repo.dir().join("input/2023/day01-alice.txt.enc"),
+            repo.dir().join("input/2023/day01.txt.enc"),
+        ]
+    );
+    assert_eq!(repo.read_input("input/2023/day01.txt"), "mine\n");
+    assert_eq!(repo.read_input("input/2023/day01-alice.txt"), "alice's\n");
+    assert!(!repo.dir().join("input/2023/day01.txt").exists());
+    assert_eq!(repo.read("input/2023/answers.txt"), "1 1 day01.txt 1\n");
+    assert_eq!(repo.read("input/2023/submissions.txt"), "1 1 1 correct 1\n");
+    assert_eq!(repo.read("input/2022/day02.txt"), "2022\n");
+
+    // The other years are encrypted with the same key.
+    let encrypted = generator::encrypt_inputs(repo.dir(), None).unwrap();
+    assert_eq!(encrypted, vec![repo.dir().join("input/2022/day02.txt.enc")]);
+    assert_eq!(repo.read_input("input/2022/day02.txt"), "2022\n");
+
+    // An input is never encrypted over one that already is.
+    std::fs::write(repo.dir().join("input/2023/day01.txt"), "again\n").unwrap();
+    assert!(generator::encrypt_inputs(repo.dir(), None).is_err());
+    assert_eq!(repo.read_input("input/2023/day01.txt"), "mine\n");
+}
@@ -0,0 +1,137 @@
+//! Parse the puzzles for the days that are already solved, and check them
+//! against the solutions generated from them.
+use std::path::{Path, PathBuf};
+
+use generator::{FileSource, Puzzle, PuzzleSource};
+use pretty_assertions::assert_eq;
+
+/// The days with a puzzle in the cache.
+const DAYS: std::ops::RangeInclusive<u32> = 1..=13;
+
+/// The root of the repo.
+fn repo() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+/// Parse the puzzle for the day from the cache in the repo.
+fn puzzle(day: u32) -> Puzzle {
+    let source = FileSource::cache(&repo());
+
+    Puzzle::parse(&source.puzzle_markdown(2023, day).unwrap()).unwrap()
+}
+
+/// The solution for the day.
+fn solution(day: u32) -> String {
+    std::fs::read_to_string(
+        repo()
+            .join("src")
+            .join("y2023")
+            .join(format!("day{:02}.rs", day)),
+    )
+    .unwrap()
+}
+
+/// The doc comment of the solve function for a part of the solution.
+fn doc_comment(solution: &str, part: u32) -> String {
+    let lines = solution.lines().collect::<Vec<_>>();
+    let fn_index = lines
+        .iter()
+        .position(|line| {
+            line.starts_with(&format!("pub fn solve_part_{}(", part))
+        })
+        .unwrap();
+    let mut comment = lines[..fn_index]
+        .iter()
+        .rev()
+        .take_while(|line| line.starts_with("///"))
+        .map(|line| {
+            let line = line.trim_start_matches("///");
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect::<Vec<_>>();
+    comment.reverse();
+
+    comment.join("\n")
+}
+
+#[test]
+fn test_titles() {
+    for day in DAYS {
+        let puzzle = puzzle(day);
+        assert_eq!(puzzle.day, day);
+
+        // The solutions were generated with the title.
+        let heading = format!("//!  Day {:02}: {}", day, puzzle.title);
+        assert_eq!(solution(day).lines().next().unwrap(), heading);
+    }
+}
+
+#[test]
+fn test_answers() {
+    let answers =
+        std::fs::read_to_string(repo().join("input/2023/answers.txt")).unwrap();
+    for day in DAYS {
+        let puzzle = puzzle(day);
+        assert_eq!(puzzle.parts.len(), 2, "day {}", day);
+
+        // The answers on the page are the ones we know for the input.
+        for part in 1..=2 {
+            let known = answers
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(|line| line.split_whitespace().collect::<Vec<_>>())
+                .find(|fields| {
+                    fields[0] == day.to_string()
+                        && fields[1] == part.to_string()
+                })
+                .map(|fields| fields[3].to_string());
+            let answer = puzzle.part(part).unwrap().answer.clone();
+            assert_eq!(answer, known, "day {} part {}", day, part);
+        }
+    }
+}
+
+#[test]
+fn test_docs() {
+    for day in DAYS {
+        let puzzle = puzzle(day);
+        let solution = solution(day);
+        for part in 1..=2 {
+            // The doc comments were generated from the text of the part, and
+            // nothing after it.
+            let doc = puzzle.doc(part).unwrap();
+            assert_eq!(doc, doc_comment(&solution, part), "day {}", day);
+            assert!(!doc.contains("Your puzzle answer was"));
+            assert!(!doc.contains("get your puzzle input"));
+        }
+    }
+}
+
+#[test]
+fn test_examples() {
+    for day in DAYS {
+        let puzzle = puzzle(day);
+        let example = puzzle.example(1).unwrap().unwrap();
+        assert!(!example.input.is_empty(), "day {}", day);
+        assert!(example.answer.parse::<i64>().is_ok(), "day {}", day);
+        assert!(puzzle.example(2).unwrap().is_some(), "day {}", day);
+    }
+
+    // Day 1 has a new example for part two, and day 2 reuses the one from
+    // part one.
+    let day01 = puzzle(1);
+    let part_one = day01.example(1).unwrap().unwrap();
+    let part_two = day01.example(2).unwrap().unwrap();
+    assert_eq!(part_one.answer, "142");
+    assert_eq!(part_two.answer, "281");
+    assert!(part_two.input.starts_with("two1nine"));
+
+    let day02 = puzzle(2);
+    assert_eq!(
+        day02.example(2).unwrap().unwrap().input,
+        day02.example(1).unwrap().unwrap().input
+    );
+}
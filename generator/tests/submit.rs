@@ -0,0 +1,203 @@
+//! Submit answers to a local server standing in for Advent of Code.
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use generator::{History, LocalServer, Outcome};
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+/// A local server that knows the right answer, and answers like Advent of
+/// Code does. It keeps the forms that were posted to it.
+struct Server {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl Server {
+    fn start(right_answer: i64) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let posted = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&mut stream);
+
+                // Read the request line and headers, then the form.
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.strip_prefix("Content-Length:")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut form = vec![0; length];
+                reader.read_exact(&mut form).unwrap();
+                let form = String::from_utf8(form).unwrap();
+                posted.lock().unwrap().push(format!(
+                    "{} {}",
+                    request_line.split_whitespace().nth(1).unwrap(),
+                    form
+                ));
+
+                let answer = form.split("answer=").nth(1).unwrap();
+                let page = match answer.parse::<i64>() {
+                    Ok(answer) if answer == right_answer => "correct.html",
+                    Ok(answer) if answer > right_answer => "too_high.html",
+                    Ok(_) => "too_low.html",
+                    Err(_) => "incorrect.html",
+                };
+                let page = std::fs::read_to_string(fixture(page)).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                    page.len(),
+                    page
+                )
+                .unwrap();
+            }
+        });
+
+        Server { url, requests }
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// A fake repo to keep the history in, in a temporary directory.
+struct Repo {
+    temp: TempDir,
+}
+
+impl Repo {
+    fn new() -> Self {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("input")).unwrap();
+        Repo { temp }
+    }
+
+    /// The root of the repo.
+    fn dir(&self) -> &Path {
+        self.temp.path()
+    }
+
+    fn history(&self) -> History {
+        History::load(&self.dir().join("input"), 2023).unwrap()
+    }
+}
+
+/// A page from the fixtures.
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("submit")
+        .join(name)
+}
+
+#[test]
+fn test_submit() {
+    let repo = Repo::new();
+    let server = Server::start(136);
+    let submitter = LocalServer::new(&server.url).unwrap();
+    let submit = |answer: &str| {
+        generator::submit(&submitter, repo.dir(), 2023, 14, 1, answer)
+    };
+
+    assert_eq!(submit("200").unwrap(), Outcome::TooHigh);
+    assert_eq!(submit("100").unwrap(), Outcome::TooLow);
+    assert_eq!(submit("abc").unwrap(), Outcome::Incorrect);
+    assert_eq!(submit(" 136\n").unwrap(), Outcome::Correct);
+    assert_eq!(
+        server.requests(),
+        vec![
+            "/2023/day/14/answer level=1&answer=200",
+            "/2023/day/14/answer level=1&answer=100",
+            "/2023/day/14/answer level=1&answer=abc",
+            "/2023/day/14/answer level=1&answer=136",
+        ]
+    );
+
+    // Every attempt is in the history, in order.
+    let attempts = repo.history().attempts;
+    assert_eq!(
+        attempts
+            .iter()
+            .map(|attempt| format!("{} {}", attempt.outcome, attempt.answer))
+            .collect::<Vec<_>>(),
+        vec![
+            "too-high 200",
+            "too-low 100",
+            "incorrect abc",
+            "correct 136"
+        ]
+    );
+    assert!(attempts.iter().all(|attempt| attempt.day == 14
+        && attempt.part == 1
+        && attempt.timestamp > 0));
+}
+
+#[test]
+fn test_never_resubmit() {
+    let repo = Repo::new();
+    let server = Server::start(136);
+    let submitter = LocalServer::new(&server.url).unwrap();
+    let submit = |part: u32, answer: &str| {
+        generator::submit(&submitter, repo.dir(), 2023, 14, part, answer)
+    };
+
+    assert_eq!(submit(1, "200").unwrap(), Outcome::TooHigh);
+    assert_eq!(submit(1, "100").unwrap(), Outcome::TooLow);
+
+    // Known wrong answers, and ones the bounds rule out, never get posted.
+    for answer in ["200", "100", "300", "50"] {
+        let e = submit(1, answer).unwrap_err();
+        assert!(
+            e.to_string()
+                .starts_with(&format!("{} is wrong for day 14", answer)),
+            "{}",
+            e
+        );
+    }
+    assert!(submit(1, "").is_err());
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(repo.history().attempts.len(), 2);
+
+    // Neither does anything once the part is solved, but the other part is
+    // its own puzzle.
+    assert_eq!(submit(1, "136").unwrap(), Outcome::Correct);
+    assert!(submit(1, "137").is_err());
+    assert_eq!(submit(2, "200").unwrap(), Outcome::TooHigh);
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[test]
+fn test_server_errors() {
+    // Nothing is recorded if the server can't be reached.
+    let repo = Repo::new();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let submitter = LocalServer::new(&url).unwrap();
+    assert!(
+        generator::submit(&submitter, repo.dir(), 2023, 14, 1, "136").is_err()
+    );
+    assert_eq!(repo.history(), History::default());
+
+    assert!(LocalServer::new("https://adventofcode.com").is_err());
+}
@@ -1,5 +1,7 @@
 //! This example shows how to add two vectors together using CUDA.
-use cuda_std::*;
+use cuda_std::kernel;
+
+use crate::thread;
 
 /// Adds two vectors together and stores the result in `c`.
 #[kernel]
@@ -0,0 +1,218 @@
+//! Run the kernels on the host by simulating a grid of blocks and threads.
+//!
+//! The kernels use [`crate::thread`] for their indices, which is
+//! `cuda_std::thread` on the GPU and the [`thread`] module below on the host.
+//! [`launch`] calls the kernel once for every thread of every block, with the
+//! indices set to that thread's position in the grid.
+//!
+//! The threads run one after another on the calling OS thread, so kernels
+//! that wait on each other with `sync_threads` can not be emulated.
+use std::cell::Cell;
+
+/// The block size to use when there is no device to suggest one.
+pub const DEFAULT_BLOCK_SIZE: u32 = 32;
+
+/// The size of a grid or a block in three dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dim {
+    /// The size in x.
+    pub x: u32,
+    /// The size in y.
+    pub y: u32,
+    /// The size in z.
+    pub z: u32,
+}
+
+impl From<u32> for Dim {
+    fn from(x: u32) -> Self {
+        Dim { x, y: 1, z: 1 }
+    }
+}
+
+impl From<(u32, u32)> for Dim {
+    fn from((x, y): (u32, u32)) -> Self {
+        Dim { x, y, z: 1 }
+    }
+}
+
+impl From<(u32, u32, u32)> for Dim {
+    fn from((x, y, z): (u32, u32, u32)) -> Self {
+        Dim { x, y, z }
+    }
+}
+
+/// The position of the thread that is currently running the kernel.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    thread_idx: Dim,
+    block_idx: Dim,
+    block_dim: Dim,
+    grid_dim: Dim,
+}
+
+std::thread_local! {
+    static CURRENT: Cell<Option<Position>> = Cell::new(None);
+}
+
+/// Clears the current position when the launch ends, even if the kernel
+/// panics.
+struct LaunchGuard;
+
+impl Drop for LaunchGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(None));
+    }
+}
+
+/// Run the kernel once for every thread of every block in the grid.
+///
+/// The kernel is usually a closure calling the `#[kernel]` function with its
+/// arguments, for example:
+///
+/// ```ignore
+/// let out_ptr = out.as_mut_ptr();
+/// launch(grid_size, block_size, || unsafe { gpu::add::add(a, b, out_ptr) });
+/// ```
+pub fn launch<G, B, F>(grid_size: G, block_size: B, mut kernel: F)
+where
+    G: Into<Dim>,
+    B: Into<Dim>,
+    F: FnMut(),
+{
+    let grid_dim = grid_size.into();
+    let block_dim = block_size.into();
+
+    let _guard = LaunchGuard;
+    for block_z in 0..grid_dim.z {
+        for block_y in 0..grid_dim.y {
+            for block_x in 0..grid_dim.x {
+                for thread_z in 0..block_dim.z {
+                    for thread_y in 0..block_dim.y {
+                        for thread_x in 0..block_dim.x {
+                            let position = Position {
+                                thread_idx: (thread_x, thread_y, thread_z)
+                                    .into(),
+                                block_idx: (block_x, block_y, block_z).into(),
+                                block_dim,
+                                grid_dim,
+                            };
+                            CURRENT.with(|current| current.set(Some(position)));
+                            kernel();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Get the position of the thread running the kernel.
+fn current() -> Position {
+    CURRENT
+        .with(|current| current.get())
+        .expect("thread index used outside of an emulated kernel launch")
+}
+
+/// Host versions of the `cuda_std::thread` functions used by the kernels.
+pub mod thread {
+    use super::current;
+
+    /// The x index of the thread in its block.
+    pub fn thread_idx_x() -> u32 {
+        current().thread_idx.x
+    }
+
+    /// The y index of the thread in its block.
+    pub fn thread_idx_y() -> u32 {
+        current().thread_idx.y
+    }
+
+    /// The z index of the thread in its block.
+    pub fn thread_idx_z() -> u32 {
+        current().thread_idx.z
+    }
+
+    /// The x index of the block in the grid.
+    pub fn block_idx_x() -> u32 {
+        current().block_idx.x
+    }
+
+    /// The y index of the block in the grid.
+    pub fn block_idx_y() -> u32 {
+        current().block_idx.y
+    }
+
+    /// The z index of the block in the grid.
+    pub fn block_idx_z() -> u32 {
+        current().block_idx.z
+    }
+
+    /// The size of a block in x.
+    pub fn block_dim_x() -> u32 {
+        current().block_dim.x
+    }
+
+    /// The size of a block in y.
+    pub fn block_dim_y() -> u32 {
+        current().block_dim.y
+    }
+
+    /// The size of a block in z.
+    pub fn block_dim_z() -> u32 {
+        current().block_dim.z
+    }
+
+    /// The size of the grid in x.
+    pub fn grid_dim_x() -> u32 {
+        current().grid_dim.x
+    }
+
+    /// The size of the grid in y.
+    pub fn grid_dim_y() -> u32 {
+        current().grid_dim.y
+    }
+
+    /// The size of the grid in z.
+    pub fn grid_dim_z() -> u32 {
+        current().grid_dim.z
+    }
+
+    /// The index of the thread in a one dimensional grid.
+    pub fn index_1d() -> u32 {
+        thread_idx_x() + block_idx_x() * block_dim_x()
+    }
+
+    /// Whether this is the first thread of the first block.
+    pub fn first() -> bool {
+        let position = current();
+        position.thread_idx == (0, 0, 0).into()
+            && position.block_idx == (0, 0, 0).into()
+    }
+
+    /// Waits for the other threads in the block, which the emulator can't do
+    /// since it runs the threads one after another.
+    pub fn sync_threads() {
+        panic!("sync_threads is not supported by the emulator");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_launch() {
+        let mut indices = Vec::new();
+        let mut first = 0;
+        super::launch(3, 4, || {
+            indices.push(super::thread::index_1d());
+            if super::thread::first() {
+                first += 1;
+            }
+        });
+
+        assert_eq!(indices, (0..12).collect::<Vec<_>>());
+        assert_eq!(first, 1);
+
+        // The indices are only available inside a launch.
+        assert!(std::panic::catch_unwind(super::thread::index_1d).is_err());
+    }
+}
@@ -28,4 +28,13 @@
 extern crate alloc;
 
 pub mod add;
-pub mod day08;
+#[cfg(not(target_os = "cuda"))]
+pub mod emulator;
+pub mod y2023;
+
+// The kernels get their thread and block indices from here, so the same
+// kernel bodies can run on the GPU or in the emulator on the host.
+#[cfg(target_os = "cuda")]
+pub use cuda_std::thread;
+#[cfg(not(target_os = "cuda"))]
+pub use emulator::thread;
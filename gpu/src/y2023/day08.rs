@@ -1,6 +1,8 @@
 //!  CUDA Kernel for day 08: Haunted Wasteland
 
-use cuda_std::*;
+use cuda_std::kernel;
+
+use crate::thread;
 
 /// Traverse the graph from nodes ending with A until all nodes reach nodes
 /// ending with Z.
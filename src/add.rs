@@ -1,74 +1,47 @@
 //! This example shows how to add two vectors together using CUDA.
 use anyhow::Result;
-use cust::prelude::*;
+
+#[cfg(feature = "cuda")]
+use crate::launch::Launch;
 
 /// Adds two vectors together and returns the result.
+#[cfg(feature = "cuda")]
 pub fn add(a: &[f32], b: &[f32]) -> Result<Vec<f32>> {
     if a.len() != b.len() {
         return Err(anyhow::anyhow!("a and b must be the same length"));
     }
 
-    // Initialize CUDA, this will pick the first available device and will
-    // make a CUDA context from it.
-    // We don't need the context for anything but it must be kept alive.
-    let _ctx = cust::quick_init()?;
-
-    // Make the CUDA module, modules just house the GPU code for the kernels we
-    // created. they can be made from PTX code, cubins, or fatbins.
-    let module = Module::from_ptx(crate::PTX, &[])?;
+    // Copy the inputs to the GPU, allocate the output buffer and launch the
+    // kernel with a thread for each element.
+    let mut launch = Launch::new("add")?;
+    launch.slice(a)?;
+    launch.slice(b)?;
+    let out = launch.output::<f32>(a.len())?;
+    let outputs = unsafe { launch.run(a.len())? };
 
-    // make a CUDA stream to issue calls to. You can think of this as an OS
-    // thread but for dispatching GPU calls.
-    let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    // copy back the data from the GPU.
+    outputs.get(out)
+}
 
-    // allocate the GPU memory needed to house our numbers and copy them over.
-    let lhs_gpu = a.as_dbuf()?;
-    let rhs_gpu = b.as_dbuf()?;
+/// Adds two vectors together on the host by running the kernel in the
+/// emulator, and returns the result.
+pub fn add_emulated(a: &[f32], b: &[f32]) -> Result<Vec<f32>> {
+    if a.len() != b.len() {
+        return Err(anyhow::anyhow!("a and b must be the same length"));
+    }
 
     let length = a.len();
-
-    // Allocate our output buffer. You could also use
-    // DeviceBuffer::uninitialized() to avoid the cost of the copy, but you
-    // need to be careful not to read from the buffer.
     let mut out = vec![0.0f32; length];
-    let out_buf = out.as_slice().as_dbuf()?;
-
-    // Retrieve the add kernel from the module so we can calculate the right
-    // launch config.
-    let func = module.get_function("add")?;
-
-    // Use the CUDA occupancy API to find an optimal launch configuration for
-    // the grid and block size. This will try to maximize how much of the
-    // GPU is used by finding the best launch configuration for the
-    // current CUDA device/architecture.
-    let (_, block_size) = func.suggested_launch_configuration(0, 0.into())?;
 
+    let block_size = gpu::emulator::DEFAULT_BLOCK_SIZE;
     let grid_size = (length as u32 + block_size - 1) / block_size;
 
-    println!(
-        "using {} blocks and {} threads per block",
-        grid_size, block_size
-    );
-
-    // Actually launch the GPU kernel. This will queue up the launch on the
-    // stream, it will not block the thread until the kernel is finished.
-    unsafe {
-        launch!(
-            // slices are passed as two parameters, the pointer and the length.
-            func<<<grid_size, block_size, 0, stream>>>(
-                lhs_gpu.as_device_ptr(),
-                lhs_gpu.len(),
-                rhs_gpu.as_device_ptr(),
-                rhs_gpu.len(),
-                out_buf.as_device_ptr(),
-            )
-        )?;
-    }
-
-    stream.synchronize()?;
-
-    // copy back the data from the GPU.
-    out_buf.copy_to(&mut out)?;
+    // The kernel writes through a raw pointer, just like it would to the
+    // device buffer.
+    let out_ptr = out.as_mut_ptr();
+    gpu::emulator::launch(grid_size, block_size, || unsafe {
+        gpu::add::add(a, b, out_ptr)
+    });
 
     Ok(out)
 }
@@ -78,6 +51,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[test]
+    #[cfg(feature = "cuda")]
     fn test_add() {
         let a = vec![1.0f32, 2.0, 3.0, 4.0];
         let b = vec![5.0f32, 6.0, 7.0, 8.0];
@@ -86,4 +60,16 @@ mod tests {
 
         assert_eq!(c, vec![6.0f32, 8.0, 10.0, 12.0]);
     }
+
+    #[test]
+    fn test_add_emulated() {
+        let a = vec![1.0f32, 2.0, 3.0, 4.0];
+        let b = vec![5.0f32, 6.0, 7.0, 8.0];
+
+        let c = super::add_emulated(&a, &b).unwrap();
+
+        assert_eq!(c, vec![6.0f32, 8.0, 10.0, 12.0]);
+
+        assert!(super::add_emulated(&a, &b[1..]).is_err());
+    }
 }
@@ -0,0 +1,162 @@
+//! A single type for the answers to every part of every day.
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+
+/// The answer to a part of a puzzle.
+///
+/// Integer answers compare equal by value, no matter which variant holds
+/// them, so `Answer::from(2u32) == Answer::from(2i64)`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    /// An unsigned integer.
+    Unsigned(u64),
+    /// A signed integer.
+    Signed(i64),
+    /// An unsigned integer too large for 64 bits.
+    Unsigned128(u128),
+    /// A signed integer too large for 64 bits.
+    Signed128(i128),
+    /// Anything that is not a number.
+    Text(String),
+}
+
+impl Answer {
+    /// Get the sign and magnitude of an integer answer.
+    fn integer(&self) -> Option<(bool, u128)> {
+        match self {
+            Answer::Unsigned(n) => Some((false, *n as u128)),
+            Answer::Signed(n) => Some((*n < 0, n.unsigned_abs() as u128)),
+            Answer::Unsigned128(n) => Some((false, *n)),
+            Answer::Signed128(n) => Some((*n < 0, n.unsigned_abs())),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.integer(), other.integer()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned128(n) => write!(f, "{}", n),
+            Answer::Signed128(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = anyhow::Error;
+
+    /// Parse an answer, picking the smallest variant that holds it.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            anyhow::bail!("empty answer");
+        }
+
+        if let Ok(n) = s.parse::<u64>() {
+            Ok(Answer::Unsigned(n))
+        } else if let Ok(n) = s.parse::<i64>() {
+            Ok(Answer::Signed(n))
+        } else if let Ok(n) = s.parse::<u128>() {
+            Ok(Answer::Unsigned128(n))
+        } else if let Ok(n) = s.parse::<i128>() {
+            Ok(Answer::Signed128(n))
+        } else {
+            Ok(Answer::Text(s.to_string()))
+        }
+    }
+}
+
+/// Implement `From` for integer types that fit in one of the variants.
+macro_rules! impl_from_integer {
+    ($variant:ident, $inner:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $inner)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(Unsigned, u64, u8, u16, u32, u64, usize);
+impl_from_integer!(Signed, i64, i8, i16, i32, i64, isize);
+impl_from_integer!(Unsigned128, u128, u128);
+impl_from_integer!(Signed128, i128, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use super::Answer;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "54450".parse::<Answer>().unwrap().to_string(),
+            "54450".to_string()
+        );
+        assert!(matches!(
+            "-1068".parse::<Answer>().unwrap(),
+            Answer::Signed(-1068)
+        ));
+        assert!(matches!(
+            "340282366920938463463374607431768211455"
+                .parse::<Answer>()
+                .unwrap(),
+            Answer::Unsigned128(u128::MAX)
+        ));
+        assert!(matches!(
+            "-170141183460469231731687303715884105728"
+                .parse::<Answer>()
+                .unwrap(),
+            Answer::Signed128(i128::MIN)
+        ));
+        assert_eq!(
+            " LRFJKXZ \n".parse::<Answer>().unwrap(),
+            Answer::from("LRFJKXZ")
+        );
+        assert!("".parse::<Answer>().is_err());
+    }
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(Answer::from(1068u32), Answer::from(1068i32));
+        assert_eq!(Answer::from(7u64), Answer::from(7u128));
+        assert_eq!(Answer::from(-2i32), Answer::from(-2i128));
+        assert_ne!(Answer::from(-2i32), Answer::from(2u32));
+        assert_ne!(Answer::from(2u32), Answer::from("2"));
+        assert_eq!(
+            "15726453850399".parse::<Answer>().unwrap(),
+            Answer::from(15726453850399u64)
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! The backends a solution can run on, and cross-checking the answers from
+//! each of them.
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+
+use crate::{Answer, Params, Solution};
+
+/// Where the code for a solution runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Backend {
+    /// Single threaded code on the CPU.
+    Serial,
+    /// Parallel code on the CPU, using rayon.
+    Rayon,
+    /// Kernels on the GPU, using CUDA. This needs the `cuda` feature.
+    Cuda,
+    /// The same kernels as `Cuda`, run on the CPU by the emulator in the
+    /// `gpu` crate.
+    Emulated,
+}
+
+impl Backend {
+    /// Every backend.
+    pub const ALL: &'static [Backend] = &[
+        Backend::Serial,
+        Backend::Rayon,
+        Backend::Cuda,
+        Backend::Emulated,
+    ];
+
+    /// The name of the backend, as used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Serial => "serial",
+            Backend::Rayon => "rayon",
+            Backend::Cuda => "cuda",
+            Backend::Emulated => "emulated",
+        }
+    }
+
+    /// Whether the backend was built in.
+    pub fn is_available(&self) -> bool {
+        *self != Backend::Cuda || cfg!(feature = "cuda")
+    }
+
+    /// Whether the backend runs when none is picked, in a cross-check or a
+    /// benchmark. The emulator runs every thread one after another, which
+    /// takes far too long on a real input, so it only runs when asked for.
+    pub fn runs_by_default(&self) -> bool {
+        self.is_available() && *self != Backend::Emulated
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Backend::ALL
+            .iter()
+            .find(|backend| backend.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "invalid backend: {}, expected one of: {}",
+                    s,
+                    Backend::ALL
+                        .iter()
+                        .map(|backend| backend.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+/// The answers from running a part of a puzzle on the backends of the
+/// solution that run by default.
+///
+/// New kernels are validated by cross-checking them against the CPU code.
+pub struct CrossCheck {
+    /// The answer, or the error, from each backend that was run.
+    pub results: Vec<(Backend, Result<Answer>)>,
+}
+
+impl CrossCheck {
+    /// Run the part on every backend of the solution that runs by default,
+    /// and on the extra one if one is given, like `Backend::Emulated`.
+    pub fn run(
+        solution: &dyn Solution,
+        part: u32,
+        input: &str,
+        params: &Params,
+        extra: Option<Backend>,
+    ) -> Result<Self> {
+        let mut backends = solution
+            .backends(part)
+            .iter()
+            .filter(|backend| backend.runs_by_default())
+            .copied()
+            .collect::<Vec<_>>();
+        if let Some(extra) = extra {
+            if !backends.contains(&extra) {
+                backends.push(extra);
+            }
+        }
+        if backends.is_empty() {
+            anyhow::bail!("no available backends for part {}", part);
+        }
+
+        let results = backends
+            .into_iter()
+            .map(|backend| {
+                (backend, solution.solve_on(part, backend, input, params))
+            })
+            .collect();
+
+        Ok(CrossCheck { results })
+    }
+
+    /// Whether every backend succeeded with the same answer.
+    pub fn agrees(&self) -> bool {
+        match self.results.first() {
+            Some((_, Ok(first))) => self
+                .results
+                .iter()
+                .all(|(_, result)| matches!(result, Ok(a) if a == first)),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for CrossCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (backend, result)) in self.results.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match result {
+                Ok(answer) => write!(f, "{}: {}", backend, answer)?,
+                Err(e) => write!(f, "{}: error: {}", backend, e)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::Backend;
+
+    #[test]
+    fn test_parse() {
+        for backend in Backend::ALL {
+            assert_eq!(backend.name().parse::<Backend>().unwrap(), *backend);
+        }
+        assert!("gpu".parse::<Backend>().is_err());
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let input = r#"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"#;
+        let day08 = crate::solution::find(2023, 8).unwrap();
+        let params = crate::Params::default();
+        let check =
+            super::CrossCheck::run(day08, 2, input, &params, None).unwrap();
+        assert!(check.agrees(), "{}", check);
+        let mut backends = check
+            .results
+            .iter()
+            .map(|(backend, _)| *backend)
+            .collect::<Vec<_>>();
+        backends.sort_unstable();
+        assert_eq!(
+            backends,
+            Backend::ALL
+                .iter()
+                .filter(|backend| backend.runs_by_default())
+                .copied()
+                .collect::<Vec<_>>()
+        );
+
+        // The emulator is only checked when it is asked for.
+        let check = super::CrossCheck::run(
+            day08,
+            2,
+            input,
+            &params,
+            Some(Backend::Emulated),
+        )
+        .unwrap();
+        assert!(check.agrees(), "{}", check);
+        assert_eq!(check.results.last().unwrap().0, Backend::Emulated);
+
+        // Answers from different backends that don't match are reported.
+        let check = super::CrossCheck {
+            results: vec![
+                (Backend::Serial, Ok(6u64.into())),
+                (Backend::Emulated, Ok(4u64.into())),
+            ],
+        };
+        assert!(!check.agrees());
+        assert_eq!(check.to_string(), "serial: 6\nemulated: 4");
+    }
+}
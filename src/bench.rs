@@ -0,0 +1,327 @@
+//! Benchmark the solutions for each day, part and backend.
+use std::{
+    fmt,
+    io::Write,
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+
+use crate::{Answer, Backend, Params, Solution};
+
+/// How many times to run each benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Runs before the samples, which are not timed.
+    pub warmup: usize,
+    /// Timed runs.
+    pub samples: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 1,
+            samples: 10,
+        }
+    }
+}
+
+/// Statistics for the timings of the samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// The median time.
+    pub median: Duration,
+    /// The fastest time.
+    pub min: Duration,
+    /// The standard deviation of the times.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Compute the statistics for the samples.
+    pub fn from_samples(samples: &[Duration]) -> Result<Self> {
+        if samples.is_empty() {
+            anyhow::bail!("no samples");
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        // Use the sample standard deviation, since the runs are a sample of
+        // every possible run.
+        let secs = samples.iter().map(|s| s.as_secs_f64()).collect::<Vec<_>>();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let stddev = if secs.len() > 1 {
+            let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>()
+                / (secs.len() - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        Ok(Stats {
+            median,
+            min: sorted[0],
+            stddev: Duration::from_secs_f64(stddev),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "median {:?}, min {:?}, stddev {:?}",
+            self.median, self.min, self.stddev
+        )
+    }
+}
+
+/// The result of benchmarking a part on a backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchResult {
+    /// The year of the puzzle.
+    pub year: u32,
+    /// The day of the puzzle.
+    pub day: u32,
+    /// The part of the puzzle.
+    pub part: u32,
+    /// The backend the part ran on.
+    pub backend: Backend,
+    /// The answer from the samples.
+    pub answer: Answer,
+    /// The number of timed samples.
+    pub samples: usize,
+    /// The time to parse the input on its own, or `None` for solutions that
+    /// parse as they go, which is part of the time to solve.
+    pub parse: Option<Stats>,
+    /// The time to solve the part, which includes parsing the input again.
+    pub solve: Stats,
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {:02} part {} on {}: {}",
+            self.year, self.day, self.part, self.backend, self.answer
+        )?;
+        if let Some(parse) = &self.parse {
+            write!(f, "\n  parse: {}", parse)?;
+        }
+        write!(f, "\n  solve: {}", self.solve)
+    }
+}
+
+/// Benchmark the part on the backend.
+pub fn bench(
+    solution: &dyn Solution,
+    part: u32,
+    backend: Backend,
+    input: &str,
+    params: &Params,
+    options: &BenchOptions,
+) -> Result<BenchResult> {
+    if options.samples == 0 {
+        anyhow::bail!("need at least one sample");
+    }
+
+    for _ in 0..options.warmup {
+        solution.parse(part, input, params)?;
+        solution.solve_on(part, backend, input, params)?;
+    }
+
+    let mut answer = None;
+    let mut parses = false;
+    let mut parse_times = Vec::with_capacity(options.samples);
+    let mut solve_times = Vec::with_capacity(options.samples);
+    for _ in 0..options.samples {
+        let start = Instant::now();
+        parses = solution.parse(part, input, params)?;
+        parse_times.push(start.elapsed());
+
+        let start = Instant::now();
+        let sample = solution.solve_on(part, backend, input, params)?;
+        solve_times.push(start.elapsed());
+
+        // Every sample should get the same answer, otherwise there is a bug
+        // in the solution.
+        match &answer {
+            Some(answer) if *answer != sample => anyhow::bail!(
+                "answers differ between samples: {} and {}",
+                answer,
+                sample
+            ),
+            Some(_) => {}
+            None => answer = Some(sample),
+        }
+    }
+
+    Ok(BenchResult {
+        year: solution.year(),
+        day: solution.day(),
+        part,
+        backend,
+        answer: answer.expect("at least one sample"),
+        samples: options.samples,
+        parse: if parses {
+            Some(Stats::from_samples(&parse_times)?)
+        } else {
+            None
+        },
+        solve: Stats::from_samples(&solve_times)?,
+    })
+}
+
+/// The header of the CSV file the results are written to.
+const CSV_HEADER: &str = "timestamp,year,day,part,backend,samples,\
+                          parse_median_ns,parse_min_ns,parse_stddev_ns,\
+                          solve_median_ns,solve_min_ns,solve_stddev_ns,answer";
+
+/// Append the results to a CSV file, writing the header first if the file is
+/// new. Each row has the time of the run in seconds since the epoch, so runs
+/// can be compared over time. The parse times are empty for solutions that
+/// parse as they go.
+pub fn append_csv(path: &Path, results: &[BenchResult]) -> Result<()> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    let new = !path.exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| {
+            anyhow::anyhow!("Failed to open {}: {}", path.display(), e)
+        })?;
+
+    if new {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+    for result in results {
+        let parse = match &result.parse {
+            Some(parse) => format!(
+                "{},{},{}",
+                parse.median.as_nanos(),
+                parse.min.as_nanos(),
+                parse.stddev.as_nanos()
+            ),
+            None => ",,".to_string(),
+        };
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            timestamp,
+            result.year,
+            result.day,
+            result.part,
+            result.backend,
+            result.samples,
+            parse,
+            result.solve.median.as_nanos(),
+            result.solve.min.as_nanos(),
+            result.solve.stddev.as_nanos(),
+            csv_field(&result.answer.to_string())
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Quote a CSV field if it has a comma or a quote in it.
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2]
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect::<Vec<_>>();
+        let stats = super::Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        // The sample standard deviation of 1, 2, 3 and 4 is sqrt(5/3).
+        assert_eq!(stats.stddev.as_micros(), 1290);
+
+        let stats = super::Stats::from_samples(&samples[..1]).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.stddev, Duration::from_secs(0));
+
+        assert!(super::Stats::from_samples(&[]).is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        let day06 = crate::solution::find(2023, 6).unwrap();
+        let input = crate::test_input!(2023, 6);
+        let options = super::BenchOptions {
+            warmup: 0,
+            samples: 3,
+        };
+        let result = super::bench(
+            day06,
+            1,
+            crate::Backend::Serial,
+            &input,
+            &crate::Params::default(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(result.year, 2023);
+        assert_eq!(result.day, 6);
+        assert_eq!(result.samples, 3);
+        assert_eq!(result.answer, 440000u64.into());
+        assert!(result.parse.is_some());
+
+        // Day 9 parses as it goes, so it has no parse time of its own.
+        let day09 = crate::solution::find(2023, 9).unwrap();
+        let example = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+        let unparsed = super::bench(
+            day09,
+            1,
+            crate::Backend::Serial,
+            example,
+            &crate::Params::default(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(unparsed.parse, None);
+        assert!(!unparsed.to_string().contains("parse"));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bench.csv");
+        super::append_csv(&path, &[result.clone()]).unwrap();
+        super::append_csv(&path, &[result, unparsed]).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], super::CSV_HEADER);
+        assert!(lines[1].ends_with(",440000"));
+        assert_eq!(lines[1].split(',').nth(1), Some("2023"));
+        assert_eq!(lines[1].split(',').nth(4), Some("serial"));
+        assert!(lines[3].contains(",serial,3,,,,"));
+        assert!(lines[3].ends_with(",114"));
+
+        assert_eq!(super::csv_field("a,b"), "\"a,b\"");
+    }
+}
@@ -0,0 +1,94 @@
+//! The CUDA context shared by every GPU solution in the process.
+use anyhow::Result;
+use cust::{context::CurrentContext, prelude::*};
+use once_cell::sync::OnceCell;
+
+/// The context for the process, created on first use.
+static CONTEXT: OnceCell<GpuContext> = OnceCell::new();
+
+/// A CUDA context with the module for our kernels loaded and a stream to
+/// launch them on.
+///
+/// Creating a context and loading the PTX is slow, so this is done once per
+/// process and shared, instead of for every kernel launch.
+pub struct GpuContext {
+    // The module and the stream belong to the context, so they are declared
+    // first to be dropped before it.
+    module: Module,
+    stream: Stream,
+    context: Context,
+}
+
+impl GpuContext {
+    /// Get the context for the process, creating it the first time this is
+    /// called.
+    ///
+    /// A CUDA context is only current on the thread that created it, so this
+    /// also makes it current on the calling thread. Call this on every thread
+    /// before using the GPU, rather than holding on to the context from
+    /// another thread.
+    pub fn get() -> Result<&'static GpuContext> {
+        let gpu = CONTEXT.get_or_try_init(GpuContext::new)?;
+        CurrentContext::set_current(&gpu.context)?;
+
+        Ok(gpu)
+    }
+
+    /// Create the context and load the module.
+    fn new() -> Result<Self> {
+        // Initialize CUDA, this will pick the first available device and will
+        // make a CUDA context from it.
+        let context = cust::quick_init()?;
+
+        // Make the CUDA module, modules just house the GPU code for the
+        // kernels we created. they can be made from PTX code, cubins, or
+        // fatbins.
+        let module = Module::from_ptx(crate::PTX, &[])?;
+
+        // make a CUDA stream to issue calls to. You can think of this as an OS
+        // thread but for dispatching GPU calls.
+        let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+
+        Ok(GpuContext {
+            module,
+            stream,
+            context,
+        })
+    }
+
+    /// Get a kernel from the module by name.
+    pub fn function(&self, name: &str) -> Result<Function<'_>> {
+        self.module.get_function(name).map_err(|e| {
+            anyhow::anyhow!("failed to get kernel {}: {}", name, e)
+        })
+    }
+
+    /// The default stream to launch kernels on.
+    ///
+    /// The stream is shared, so `synchronize` waits for the kernels launched
+    /// by every thread, not only the calling one.
+    pub fn stream(&self) -> &Stream {
+        &self.stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_get() {
+        let a = super::GpuContext::get().unwrap() as *const _;
+        let b = super::GpuContext::get().unwrap() as *const _;
+        assert_eq!(a, b);
+
+        // The context can be used from another thread too.
+        std::thread::spawn(|| {
+            let gpu = super::GpuContext::get().unwrap();
+            gpu.function("add").unwrap();
+            assert!(gpu.function("missing").is_err());
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,442 @@
+//! A grid of cells, for the puzzles that are a map of characters.
+//!
+//! Positions are `(x, y)`, with `x` the column from the left and `y` the row
+//! from the top, like the puzzles draw them.
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use anyhow::Result;
+
+/// A position in the grid, as `(x, y)`.
+pub type Position = (usize, usize);
+
+/// The offsets to the neighbours that share a side with a cell.
+const SIDES: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets to every neighbour of a cell, including the diagonals.
+const AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    /// The number of columns.
+    width: usize,
+    /// The number of rows.
+    height: usize,
+    /// The cells, row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Make a grid from its cells, row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if cells.len() != width * height {
+            anyhow::bail!(
+                "{} cells don't make a {}x{} grid",
+                cells.len(),
+                width,
+                height
+            );
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parse a grid from text, with a line for each row, turning each
+    /// character into a cell.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T>,
+    ) -> Result<Self> {
+        Grid::parse_with_position(input, |_, c| cell(c))
+    }
+
+    /// Parse a grid from text like [`Grid::parse`], for cells that need to
+    /// know where they are.
+    pub fn parse_with_position(
+        input: &str,
+        mut cell: impl FnMut(Position, char) -> Result<T>,
+    ) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let mut x = 0;
+            for c in line.chars() {
+                cells.push(cell((x, y), c)?);
+                x += 1;
+            }
+
+            match width {
+                None => width = Some(x),
+                Some(width) if width != x => anyhow::bail!(
+                    "row {} is {} wide, the rows before it are {}",
+                    y + 1,
+                    x,
+                    width
+                ),
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Grid::new(width.unwrap_or(0), height, cells)
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the cell at the position, if it is in the grid.
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Get the cell at the position to change it, if it is in the grid.
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the positions and cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// Iterate over the positions and cells to change them, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// The neighbours that share a side with the position, and are in the
+    /// grid, clockwise from the one above.
+    pub fn neighbours4(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, &SIDES)
+    }
+
+    /// Every neighbour of the position that is in the grid, including the
+    /// diagonals, clockwise from the top left.
+    pub fn neighbours8(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, &AROUND)
+    }
+
+    /// The positions at the offsets from the position that are in the grid.
+    fn offsets<'a>(
+        &'a self,
+        (x, y): Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let x = x as isize + dx;
+            let y = y as isize + dy;
+            if x < 0 || y < 0 || x as usize >= self.width {
+                return None;
+            }
+            if y as usize >= self.height {
+                return None;
+            }
+
+            Some((x as usize, y as usize))
+        })
+    }
+
+    /// Get a row, if it is in the grid.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the rows, from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).filter_map(move |y| self.row(y))
+    }
+
+    /// Iterate over the cells of a column, from the top. A column that isn't
+    /// in the grid has no cells.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(height)
+    }
+
+    /// Iterate over the columns, from the left.
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T>> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Make a grid from this one, changing each cell.
+    pub fn map<U>(&self, mut cell: impl FnMut(Position, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(position, c)| cell(position, c)).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Make a grid with every cell the same.
+    pub fn filled(width: usize, height: usize, cell: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// Swap the rows and the columns.
+    pub fn transpose(&self) -> Self {
+        let cells = self.columns().flatten().cloned().collect();
+
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Turn the grid a quarter turn clockwise, so the left column becomes the
+    /// top row.
+    pub fn rotate_clockwise(&self) -> Self {
+        let cells = self
+            .columns()
+            .flat_map(|column| column.collect::<Vec<_>>().into_iter().rev())
+            .cloned()
+            .collect();
+
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Turn the grid a quarter turn counterclockwise, so the right column
+    /// becomes the top row.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x))
+            .cloned()
+            .collect();
+
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{:?} is outside the {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!("{:?} is outside the {}x{} grid", position, width, height)
+        })
+    }
+}
+
+/// Draw the grid like the puzzle input, a line for each row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::Grid;
+
+    /// The grid of the example, with each character as a cell.
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef", Ok).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits =
+            Grid::parse("12\n34\n", |c| Ok(c.to_digit(10).unwrap())).unwrap();
+        assert_eq!(digits.row(1), Some(&[3, 4][..]));
+
+        let positions =
+            Grid::parse_with_position("..\n..", |position, _| Ok(position))
+                .unwrap();
+        assert_eq!(positions[(1, 0)], (1, 0));
+
+        // Every row is as wide as the first, and the cells can fail to parse.
+        let e = Grid::parse("abc\nde", Ok).unwrap_err();
+        assert_eq!(e.to_string(), "row 2 is 2 wide, the rows before it are 3");
+        assert!(Grid::<u32>::parse("1x", |c| c
+            .to_digit(10)
+            .ok_or_else(|| anyhow::anyhow!("not a digit: {}", c)))
+        .is_err());
+
+        let empty = Grid::parse("", Ok).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(empty.columns().count(), 0);
+
+        assert!(Grid::new(2, 2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = example();
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+
+        *grid.get_mut((0, 1)).unwrap() = 'x';
+        grid[(1, 1)] = 'y';
+        assert_eq!(grid.to_string(), "abc\nxyf");
+        assert_eq!(grid.get_mut((3, 3)), None);
+
+        for (_, cell) in grid.iter_mut() {
+            *cell = cell.to_ascii_uppercase();
+        }
+        assert_eq!(
+            grid.iter().filter(|(_, c)| **c == 'Y').collect::<Vec<_>>(),
+            vec![((1, 1), &'Y')]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside the 3x2 grid")]
+    fn test_index_outside() {
+        let _ = example()[(3, 0)];
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::filled(3, 3, '.');
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+
+        // Only the neighbours in the grid.
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = example();
+        assert_eq!(grid.row(0), Some(&['a', 'b', 'c'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = example();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+
+        let turned =
+            (0..4).fold(grid.clone(), |grid, _| grid.rotate_clockwise());
+        assert_eq!(turned, grid);
+        assert_eq!(
+            grid.map(|(x, y), c| format!("{}{}{}", c, x, y)).row(1),
+            Some(
+                &["d01".to_string(), "e11".to_string(), "f21".to_string()][..]
+            )
+        );
+    }
+}
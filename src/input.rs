@@ -0,0 +1,129 @@
+//! Read the inputs, which are kept encrypted in `input/YYYY/dayNN.txt.enc` so
+//! the personal inputs aren't public.
+//!
+//! The key is 32 bytes in hex, from `AOC_INPUT_KEY` or the `.input-key` file
+//! in the repo, which is never checked in. A plain `dayNN.txt` is read as it
+//! is, so inputs that aren't encrypted yet still work.
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+pub use aoc_input::{
+    encrypted_path, Key, ENCRYPTED_EXTENSION, KEY_FILE, KEY_VAR,
+};
+
+/// The root of the repo, where the key file is.
+pub fn repo_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// The checked in input for the year and day, before it is encrypted.
+pub fn path(year: u32, day: u32) -> PathBuf {
+    repo_dir()
+        .join("input")
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Read the input at the path, or decrypt the encrypted one next to it with
+/// the key for the repo.
+pub fn read(path: &Path) -> Result<String> {
+    let encrypted_path = encrypted_path(path);
+    if path.exists() || !encrypted_path.exists() {
+        return std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!("Failed to read {}: {}", path.display(), e)
+        });
+    }
+
+    let key = Key::load(repo_dir())?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No key to decrypt {}, set {} or put it in {}",
+            encrypted_path.display(),
+            KEY_VAR,
+            KEY_FILE
+        )
+    })?;
+    let encrypted = std::fs::read(&encrypted_path)?;
+
+    key.decrypt(&encrypted)
+        .map_err(|e| anyhow::anyhow!("{} at {}", e, encrypted_path.display()))
+}
+
+/// The input for a test, or `None` if it is encrypted and there's no key to
+/// decrypt it, so the test can be skipped.
+pub fn for_test(year: u32, day: u32) -> Option<String> {
+    let path = path(year, day);
+    let has_key = match Key::load(repo_dir()) {
+        Ok(key) => key.is_some(),
+        Err(e) => panic!("{}", e),
+    };
+    if !path.exists() && !has_key {
+        // Write to stderr itself, since the test harness hides what the tests
+        // print when they pass.
+        let _ = writeln!(
+            std::io::stderr(),
+            "Skipping the test with the input for {} day {}: no key to \
+             decrypt it, set {} or put it in {}",
+            year,
+            day,
+            KEY_VAR,
+            KEY_FILE
+        );
+        return None;
+    }
+
+    match read(&path) {
+        Ok(input) => Some(input),
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Get the input for the year and day in a test, or return from the test if
+/// there's no key to decrypt it.
+#[macro_export]
+macro_rules! test_input {
+    ($year:expr, $day:expr) => {
+        match $crate::input::for_test($year, $day) {
+            Some(input) => input,
+            None => return,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_decrypt_inputs() {
+        // CI gets the key in `AOC_INPUT_KEY` from a secret. Check it against
+        // every encrypted input, even one with a plain copy next to it, so a
+        // key that no longer works fails here, on its own.
+        if std::env::var(super::KEY_VAR)
+            .map_or(true, |key| key.trim().is_empty())
+        {
+            return;
+        }
+        let key = super::Key::load(super::repo_dir()).unwrap().unwrap();
+
+        let mut decrypted = 0;
+        let input_dir = super::repo_dir().join("input");
+        for year_dir in std::fs::read_dir(input_dir).unwrap() {
+            for entry in std::fs::read_dir(year_dir.unwrap().path()).unwrap() {
+                let path = entry.unwrap().path();
+                if path.extension().and_then(|e| e.to_str())
+                    != Some(super::ENCRYPTED_EXTENSION)
+                {
+                    continue;
+                }
+
+                let encrypted = std::fs::read(&path).unwrap();
+                if let Err(e) = key.decrypt(&encrypted) {
+                    panic!("{} at {}", e, path.display());
+                }
+                decrypted += 1;
+            }
+        }
+        assert!(decrypted > 0, "no encrypted inputs");
+    }
+}
@@ -0,0 +1,183 @@
+//! A typed helper for launching kernels. It copies the inputs to the GPU,
+//! sizes the grid, launches the kernel and copies the outputs back.
+use std::{any::Any, ffi::c_void, marker::PhantomData};
+
+use anyhow::Result;
+use cust::{memory::DeviceCopy, prelude::*};
+
+use crate::context::GpuContext;
+
+/// A kernel launch, built up by adding the arguments in the order the kernel
+/// takes them.
+///
+/// For example, for the `add(a: &[f32], b: &[f32], c: *mut f32)` kernel:
+///
+/// ```ignore
+/// let mut launch = Launch::new("add")?;
+/// launch.slice(a)?;
+/// launch.slice(b)?;
+/// let c = launch.output::<f32>(a.len())?;
+/// let outputs = unsafe { launch.run(a.len())? };
+/// let c = outputs.get(c)?;
+/// ```
+pub struct Launch {
+    /// The shared context to launch the kernel in.
+    gpu: &'static GpuContext,
+    /// The name of the kernel in the module.
+    name: String,
+    /// The buffers on the GPU, which must live until the kernel is finished.
+    buffers: Vec<Box<dyn Any>>,
+    /// The values of the kernel parameters. The launch takes a pointer to
+    /// each of them.
+    args: Vec<Box<dyn Any>>,
+}
+
+/// An output buffer of a launch, used to get its contents after the launch.
+#[derive(Debug)]
+pub struct Output<T> {
+    /// The index of the buffer in the launch.
+    index: usize,
+    _marker: PhantomData<T>,
+}
+
+/// The output buffers of a finished launch.
+pub struct Outputs {
+    buffers: Vec<Box<dyn Any>>,
+}
+
+impl Launch {
+    /// Start a launch of the kernel with the given name.
+    pub fn new(name: &str) -> Result<Self> {
+        Ok(Launch {
+            gpu: GpuContext::get()?,
+            name: name.to_string(),
+            buffers: Vec::new(),
+            args: Vec::new(),
+        })
+    }
+
+    /// Copy a slice to the GPU and pass it to the kernel. Slices are passed as
+    /// two parameters, the pointer and the length.
+    pub fn slice<T>(&mut self, slice: &[T]) -> Result<&mut Self>
+    where
+        T: DeviceCopy + 'static,
+    {
+        let buffer = slice.as_dbuf()?;
+        self.args.push(Box::new(buffer.as_device_ptr()));
+        self.args.push(Box::new(buffer.len()));
+        self.buffers.push(Box::new(buffer));
+
+        Ok(self)
+    }
+
+    /// Pass a value to the kernel.
+    pub fn value<T>(&mut self, value: T) -> &mut Self
+    where
+        T: DeviceCopy + 'static,
+    {
+        self.args.push(Box::new(value));
+        self
+    }
+
+    /// Allocate an output buffer of the given length, set to the default
+    /// value, and pass the pointer to it to the kernel.
+    pub fn output<T>(&mut self, len: usize) -> Result<Output<T>>
+    where
+        T: DeviceCopy + Default + Clone + 'static,
+    {
+        let buffer = vec![T::default(); len].as_slice().as_dbuf()?;
+        self.args.push(Box::new(buffer.as_device_ptr()));
+        self.buffers.push(Box::new(buffer));
+
+        Ok(Output {
+            index: self.buffers.len() - 1,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Launch the kernel with at least the given number of threads and wait
+    /// for it to finish.
+    ///
+    /// # Safety
+    ///
+    /// The arguments must match the parameters of the kernel, in order and in
+    /// type, and the kernel must only access the outputs within their length.
+    pub unsafe fn run(self, threads: usize) -> Result<Outputs> {
+        let func = self.gpu.function(&self.name)?;
+        let stream = self.gpu.stream();
+
+        // Use the CUDA occupancy API to find an optimal launch configuration
+        // for the grid and block size. This will try to maximize how much of
+        // the GPU is used by finding the best launch configuration for the
+        // current CUDA device/architecture.
+        let (_, block_size) =
+            func.suggested_launch_configuration(0, 0.into())?;
+
+        let grid_size = (threads as u32 + block_size - 1) / block_size;
+
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.as_ref() as *const dyn Any as *mut c_void)
+            .collect::<Vec<_>>();
+
+        // This will queue up the launch on the stream, it will not block the
+        // thread until the kernel is finished.
+        stream.launch(&func, grid_size, block_size, 0, &args)?;
+
+        stream.synchronize()?;
+
+        Ok(Outputs {
+            buffers: self.buffers,
+        })
+    }
+}
+
+impl Outputs {
+    /// Copy an output buffer back from the GPU.
+    pub fn get<T>(&self, output: Output<T>) -> Result<Vec<T>>
+    where
+        T: DeviceCopy + Default + Clone + 'static,
+    {
+        let buffer = self
+            .buffers
+            .get(output.index)
+            .and_then(|buffer| buffer.downcast_ref::<DeviceBuffer<T>>())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "output {} is not from this launch",
+                    output.index
+                )
+            })?;
+
+        let mut out = vec![T::default(); buffer.len()];
+        buffer.copy_to(&mut out)?;
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_launch() {
+        let a = (0..1000).map(|i| i as f32).collect::<Vec<_>>();
+        let b = vec![1.0f32; 1000];
+
+        let mut launch = super::Launch::new("add").unwrap();
+        launch.slice(&a).unwrap().slice(&b).unwrap();
+        let c = launch.output::<f32>(a.len()).unwrap();
+        let outputs = unsafe { launch.run(a.len()).unwrap() };
+
+        assert_eq!(
+            outputs.get(c).unwrap(),
+            (1..1001).map(|i| i as f32).collect::<Vec<_>>()
+        );
+
+        // Unknown kernels fail when launched.
+        let launch = super::Launch::new("missing").unwrap();
+        assert!(unsafe { launch.run(1) }.is_err());
+    }
+}
@@ -2,24 +2,46 @@
 
 #![deny(missing_docs)]
 
-#[cfg(not(target_os = "macos"))]
 pub mod add;
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-#[cfg(not(target_os = "macos"))]
-pub mod gpu_day08;
+pub mod answer;
+pub mod backend;
+pub mod bench;
+#[cfg(feature = "cuda")]
+pub mod context;
+pub mod grid;
+pub mod input;
+#[cfg(feature = "cuda")]
+pub mod launch;
+pub mod manifest;
+pub mod params;
+pub mod solution;
+pub mod status;
+pub mod y2023;
+
+pub use answer::Answer;
+pub use backend::{Backend, CrossCheck};
+pub use grid::Grid;
+pub use manifest::{Manifest, Status};
+pub use params::Params;
+pub use solution::{Solution, Unimplemented};
 
 /// The PTX code for the GPU kernel.
-#[cfg(not(target_os = "macos"))]
+#[cfg(feature = "cuda")]
 pub(crate) static PTX: &str = include_str!("../resources/gpu.ptx");
+
+/// Every registered solution, in order of year and day.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &y2023::day01::Day01,
+    &y2023::day02::Day02,
+    &y2023::day03::Day03,
+    &y2023::day04::Day04,
+    &y2023::day05::Day05,
+    &y2023::day06::Day06,
+    &y2023::day07::Day07,
+    &y2023::day08::Day08,
+    &y2023::day09::Day09,
+    &y2023::day10::Day10,
+    &y2023::day11::Day11,
+    &y2023::day12::Day12,
+    &y2023::day13::Day13,
+];
@@ -0,0 +1,472 @@
+//! This program runs the solution for a given day and part and prints the
+//! answer, benchmarks the solutions, verifies them against the known answers,
+//! or shows the state of every day of a year.
+
+#![deny(missing_docs)]
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use advent_of_cuda::*;
+use anyhow::Result;
+
+/// The usage string for the runner.
+const USAGE: &str = "Usage: advent-of-cuda [--year year] \
+                     [--backend name | --gpu | --check] \
+                     [--param name=value]... <day> <part> [input]
+       advent-of-cuda bench [--year year] [--backend name] [--warmup n] \
+                     [--samples n] [--output path] [--param name=value]... \
+                     [day] [part]
+       advent-of-cuda verify [--year year] [day]
+       advent-of-cuda status [--year year] [--verify] [--bench path]
+
+The year defaults to the latest one with solutions.
+The input defaults to `input/YYYY/dayNN.txt`, pass `-` to read it from stdin.
+Encrypted inputs, `dayNN.txt.enc`, are decrypted with the key in
+`AOC_INPUT_KEY` or `.input-key`.
+Pass `--backend` to run the part on one of the backends: serial, rayon, cuda
+or emulated. `--gpu` is short for `--backend cuda`.
+Pass `--check` to run the part on every available backend but the emulator,
+which is too slow for a real input, and fail if the answers don't agree.
+`--check --backend emulated` checks the emulator too.
+Pass `--param` to override an option of the puzzle, for example
+`--param multiplier=100` for day 11.
+
+`bench` times the given day and part, or all of them for the year or every
+year, on every available backend but the emulator, or only the one passed
+with `--backend`, like `--backend emulated`. It does 1 warm-up run and 10
+samples by default, and appends the results to `bench.csv` unless `--output`
+is passed. The parse time is only there for the days that parse the input on
+their own, otherwise it is part of the solve time. A day without an input is
+reported and skipped.
+
+`verify` runs every day and part with a known answer in
+`input/YYYY/answers.txt`, for the year or every year, or only the ones for the
+given day, and prints whether each one passed, failed, regressed or is
+unimplemented.
+
+`status` shows every day of the year: whether the input is there, how far
+along each part is, whether there is a GPU kernel, and the latest benchmark
+from `bench.csv`, or the file passed with `--bench`. A part is todo, solved,
+answered once its answer is known, and verified or wrong with `--verify`,
+which runs the solutions for the known answers.";
+
+fn main() -> Result<()> {
+    quiet_unimplemented_panics();
+
+    let mut args = std::env::args().skip(1).peekable();
+    match args.peek().map(|arg| arg.as_str()) {
+        Some("bench") => {
+            args.next();
+            return bench(args);
+        }
+        Some("verify") => {
+            args.next();
+            return verify(args);
+        }
+        Some("status") => {
+            args.next();
+            return status(args);
+        }
+        _ => {}
+    }
+
+    // Parse the flags and the positional arguments from the command line.
+    let mut year = None;
+    let mut backend = None;
+    let mut check = false;
+    let mut params = Params::default();
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--backend" => {
+                backend = Some(flag_value(&mut args, &arg)?.parse()?);
+            }
+            "--gpu" => backend = Some(Backend::Cuda),
+            "--check" => check = true,
+            "--param" => params.parse_pair(&flag_value(&mut args, &arg)?)?,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let year = year_or_latest(year)?;
+    let day = positional
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("No day provided\n\n{}", USAGE))?
+        .parse::<u32>()?;
+    let part = positional
+        .get(1)
+        .ok_or_else(|| anyhow::anyhow!("No part provided\n\n{}", USAGE))?
+        .parse::<u32>()?;
+
+    let solution = solution::find(year, day).ok_or_else(|| {
+        anyhow::anyhow!("No solution for {} day {}", year, day)
+    })?;
+
+    // Read the input for the day.
+    let path = positional.get(2).map(|s| s.as_str());
+    let input = read_input(year, day, path)?;
+
+    if check {
+        return cross_check(solution, part, backend, path, &input, &params);
+    }
+
+    let answer = match backend {
+        Some(backend) => solution.solve_on(part, backend, &input, &params),
+        None => solution.solve(part, &input, &params),
+    }
+    .map_err(|e| {
+        if solution::is_unimplemented(&e) {
+            anyhow::anyhow!(
+                "{} day {} part {} is not yet solved",
+                year,
+                day,
+                part
+            )
+        } else {
+            e
+        }
+    })?;
+
+    println!("{}", answer);
+
+    // Warn if there is a known answer for the input and this isn't it. The
+    // parameters change the answer, so only check without them.
+    if params.is_empty() {
+        if let Some(name) = manifest_input(year, day, path) {
+            let manifest = Manifest::load(&input_dir(), year)?;
+            match manifest.answer(day, part, &name) {
+                Some(expected) if *expected != answer => eprintln!(
+                    "Warning: the known answer for {} is {}",
+                    name, expected
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the solutions against every known answer in the manifests, or the
+/// ones for the given year and day.
+fn verify(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut year = None;
+    let mut day = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(flag_value(&mut args, &arg)?.parse()?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => day = Some(arg.parse::<u32>()?),
+        }
+    }
+
+    // A day is only a day of the latest year, unless the year is given.
+    let years = match (year, day) {
+        (Some(year), _) => vec![year],
+        (None, Some(_)) => vec![year_or_latest(None)?],
+        (None, None) => solution::years(),
+    };
+
+    let input_dir = input_dir();
+    let mut entries = Vec::new();
+    for year in years {
+        entries.extend(Manifest::load(&input_dir, year)?.entries);
+    }
+
+    let (mut passed, mut failed, mut regressed, mut unimplemented) =
+        (0, 0, 0, 0);
+    for entry in entries
+        .iter()
+        .filter(|entry| day.map_or(true, |day| entry.day == day))
+    {
+        let verification = manifest::verify(entry, &input_dir);
+        println!("{}", verification);
+        match verification.status {
+            Status::Pass => passed += 1,
+            Status::Fail(_) => failed += 1,
+            Status::Regressed(_) => regressed += 1,
+            Status::Unimplemented => unimplemented += 1,
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} regressed, {} unimplemented",
+        passed, failed, regressed, unimplemented
+    );
+
+    if failed + regressed > 0 {
+        anyhow::bail!("{} answers did not verify", failed + regressed);
+    }
+
+    Ok(())
+}
+
+/// Show the state of every day of the year.
+fn status(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut year = None;
+    let mut verify = false;
+    let mut bench_csv = PathBuf::from("bench.csv");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--verify" => verify = true,
+            "--bench" => bench_csv = flag_value(&mut args, &arg)?.into(),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let year = year_or_latest(year)?;
+    let mut calendar =
+        status::Calendar::scan(input::repo_dir(), year, &bench_csv)?;
+    if verify {
+        calendar.verify(&input_dir())?;
+    }
+    print!("{}", calendar);
+
+    Ok(())
+}
+
+/// Benchmark the given day and part, or every one, on each backend that runs
+/// by default, or the one passed with `--backend`.
+fn bench(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut year = None;
+    let mut backend = None;
+    let mut options = bench::BenchOptions::default();
+    let mut output = PathBuf::from("bench.csv");
+    let mut params = Params::default();
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--backend" => {
+                backend = Some(flag_value(&mut args, &arg)?.parse()?);
+            }
+            "--warmup" => {
+                options.warmup = flag_value(&mut args, &arg)?.parse()?
+            }
+            "--samples" => {
+                options.samples = flag_value(&mut args, &arg)?.parse()?
+            }
+            "--output" => output = flag_value(&mut args, &arg)?.into(),
+            "--param" => params.parse_pair(&flag_value(&mut args, &arg)?)?,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let solutions = match positional.first() {
+        Some(day) => {
+            let year = year_or_latest(year)?;
+            let day = day.parse::<u32>()?;
+            vec![solution::find(year, day).ok_or_else(|| {
+                anyhow::anyhow!("No solution for {} day {}", year, day)
+            })?]
+        }
+        None => SOLUTIONS
+            .iter()
+            .filter(|solution| {
+                year.map_or(true, |year| solution.year() == year)
+            })
+            .copied()
+            .collect(),
+    };
+    let parts = match positional.get(1) {
+        Some(part) => vec![part.parse::<u32>()?],
+        None => vec![1, 2],
+    };
+
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for solution in solutions {
+        let input = match read_input(solution.year(), solution.day(), None) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!(
+                    "{} day {:02}: error: {}",
+                    solution.year(),
+                    solution.day(),
+                    e
+                );
+                failed += 1;
+                continue;
+            }
+        };
+        for part in &parts {
+            let backends = match backend {
+                Some(backend) => vec![backend],
+                None => solution
+                    .backends(*part)
+                    .iter()
+                    .filter(|backend| backend.runs_by_default())
+                    .copied()
+                    .collect(),
+            };
+
+            for backend in backends {
+                match bench::bench(
+                    solution, *part, backend, &input, &params, &options,
+                ) {
+                    Ok(result) => {
+                        println!("{}", result);
+                        results.push(result);
+                    }
+                    Err(e) if solution::is_unimplemented(&e) => {
+                        println!(
+                            "{} day {:02} part {} on {}: not yet solved",
+                            solution.year(),
+                            solution.day(),
+                            part,
+                            backend
+                        );
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "{} day {:02} part {} on {}: error: {}",
+                            solution.year(),
+                            solution.day(),
+                            part,
+                            backend,
+                            e
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    bench::append_csv(&output, &results)?;
+    println!("Wrote {} results to {}", results.len(), output.display());
+
+    if failed > 0 {
+        anyhow::bail!("{} benchmarks failed", failed);
+    }
+
+    Ok(())
+}
+
+/// Keep the panic hook quiet for `todo!()` and `unimplemented!()`, since
+/// those parts are reported as not yet solved instead.
+fn quiet_unimplemented_panics() {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !solution::is_unimplemented_panic(info.payload()) {
+            default(info);
+        }
+    }));
+}
+
+/// Get the value that follows a flag on the command line.
+fn flag_value(
+    args: &mut impl Iterator<Item = String>,
+    flag: &str,
+) -> Result<String> {
+    args.next()
+        .ok_or_else(|| anyhow::anyhow!("No value for {}\n\n{}", flag, USAGE))
+}
+
+/// Run the part on every backend that runs by default, and the one passed
+/// with `--backend`, print the answers and fail if they don't agree.
+fn cross_check(
+    solution: &dyn Solution,
+    part: u32,
+    backend: Option<Backend>,
+    path: Option<&str>,
+    input: &str,
+    params: &Params,
+) -> Result<()> {
+    let check = CrossCheck::run(solution, part, input, params, backend)?;
+
+    println!("{}", check);
+
+    if !check.agrees() {
+        // Say which input caused it, so it can be turned into a test.
+        let source = match path {
+            Some("-") => format!("stdin:\n{}", input),
+            Some(path) => path.to_string(),
+            None => format!(
+                "input/{}/day{:02}.txt",
+                solution.year(),
+                solution.day()
+            ),
+        };
+        let params = if params.is_empty() {
+            String::new()
+        } else {
+            format!(" and params {}", params)
+        };
+        anyhow::bail!(
+            "Backends disagree on {} day {} part {}{} with input {}",
+            solution.year(),
+            solution.day(),
+            part,
+            params,
+            source
+        );
+    }
+
+    Ok(())
+}
+
+/// Get the year that was passed, or the latest one with solutions.
+fn year_or_latest(year: Option<u32>) -> Result<u32> {
+    year.or_else(solution::latest_year)
+        .ok_or_else(|| anyhow::anyhow!("No solutions registered"))
+}
+
+/// The directory with a directory for each year, with the checked in inputs
+/// and the manifest of answers for the year.
+fn input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+}
+
+/// The name the input has in the manifest for the year, if it is one of the
+/// checked in inputs.
+fn manifest_input(year: u32, day: u32, path: Option<&str>) -> Option<String> {
+    match path {
+        None => Some(format!("day{:02}.txt", day)),
+        Some("-") => None,
+        Some(path) => {
+            let path = Path::new(path).canonicalize().ok()?;
+            let year_dir = input_dir().join(year.to_string());
+            if path.parent()? == year_dir.canonicalize().ok()? {
+                Some(path.file_name()?.to_str()?.to_string())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Read the input from the given path, from stdin if the path is `-`, or
+/// from the checked in input for the year and day if no path was given.
+/// Encrypted inputs are decrypted with the key for the repo.
+fn read_input(year: u32, day: u32, path: Option<&str>) -> Result<String> {
+    match path {
+        Some("-") => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => input::read(Path::new(path)),
+        None => input::read(&input::path(year, day)),
+    }
+}
@@ -0,0 +1,274 @@
+//! The manifest of known answers for each day, part and input, and verifying
+//! the solutions against it.
+use std::{fmt, path::Path};
+
+use anyhow::Result;
+
+use crate::{Answer, Params};
+
+/// The name of the manifest in the input directory for each year.
+pub const MANIFEST_FILE: &str = "answers.txt";
+
+/// A known answer in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The year of the puzzle.
+    pub year: u32,
+    /// The day of the puzzle.
+    pub day: u32,
+    /// The part of the puzzle.
+    pub part: u32,
+    /// The name of the input file, in the input directory for the year.
+    pub input: String,
+    /// The answer for the input.
+    pub answer: Answer,
+}
+
+/// The known answers for a year, keyed by day, part and input file.
+///
+/// Each line of the file is `day part input answer`, blank lines and lines
+/// starting with `#` are skipped. A day can have more than one input, since
+/// everyone gets their own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    /// The entries, in the order of the file.
+    pub entries: Vec<Entry>,
+}
+
+impl Manifest {
+    /// Parse the manifest for the year.
+    pub fn parse(year: u32, s: &str) -> Result<Self> {
+        let mut entries: Vec<Entry> = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let entry = parse_entry(year, line).map_err(|e| {
+                anyhow::anyhow!("invalid answer on line {}: {}", i + 1, e)
+            })?;
+            if entries.iter().any(|other| {
+                other.day == entry.day
+                    && other.part == entry.part
+                    && other.input == entry.input
+            }) {
+                anyhow::bail!(
+                    "duplicate answer on line {} for day {} part {} with {}",
+                    i + 1,
+                    entry.day,
+                    entry.part,
+                    entry.input
+                );
+            }
+            entries.push(entry);
+        }
+
+        Ok(Manifest { entries })
+    }
+
+    /// Load the manifest for the year from its directory in the input
+    /// directory. A year without a manifest has no known answers yet.
+    pub fn load(input_dir: &Path, year: u32) -> Result<Self> {
+        let path = input_dir.join(year.to_string()).join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Manifest::default());
+        }
+        let s = std::fs::read_to_string(&path).map_err(|e| {
+            anyhow::anyhow!("Failed to read {}: {}", path.display(), e)
+        })?;
+
+        Manifest::parse(year, &s)
+    }
+
+    /// Get the known answer for the day, part and input file.
+    pub fn answer(&self, day: u32, part: u32, input: &str) -> Option<&Answer> {
+        self.entries
+            .iter()
+            .find(|entry| {
+                entry.day == day && entry.part == part && entry.input == input
+            })
+            .map(|entry| &entry.answer)
+    }
+}
+
+/// Parse a `day part input answer` line.
+fn parse_entry(year: u32, line: &str) -> Result<Entry> {
+    let mut fields = line.splitn(4, char::is_whitespace);
+    let mut next = |name: &str| {
+        fields
+            .next()
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("missing {}", name))
+    };
+
+    let day = next("day")?.parse::<u32>()?;
+    let part = next("part")?.parse::<u32>()?;
+    let input = next("input")?.to_string();
+    let answer = next("answer")?.parse::<Answer>()?;
+    if part != 1 && part != 2 {
+        anyhow::bail!("invalid part: {}", part);
+    }
+
+    Ok(Entry {
+        year,
+        day,
+        part,
+        input,
+        answer,
+    })
+}
+
+/// The result of checking a solution against a known answer.
+#[derive(Debug)]
+pub enum Status {
+    /// The solution got the known answer.
+    Pass,
+    /// The solution returned an error.
+    Fail(anyhow::Error),
+    /// There is no solution for the day, or the part is not solved yet.
+    Unimplemented,
+    /// The solution got a different answer than the known one.
+    Regressed(Answer),
+}
+
+/// The verification of a solution against an entry in the manifest.
+#[derive(Debug)]
+pub struct Verification<'a> {
+    /// The entry that was checked.
+    pub entry: &'a Entry,
+    /// The result.
+    pub status: Status,
+}
+
+impl fmt::Display for Verification<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {:02} part {} {}: ",
+            self.entry.year, self.entry.day, self.entry.part, self.entry.input
+        )?;
+        match &self.status {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail(e) => write!(f, "fail: {}", e),
+            Status::Unimplemented => write!(f, "unimplemented"),
+            Status::Regressed(answer) => write!(
+                f,
+                "regressed: got {}, expected {}",
+                answer, self.entry.answer
+            ),
+        }
+    }
+}
+
+/// Check the solution for the entry, reading the input from the directory
+/// for the year in the input directory.
+pub fn verify<'a>(entry: &'a Entry, input_dir: &Path) -> Verification<'a> {
+    let status = match crate::solution::find(entry.year, entry.day) {
+        None => Status::Unimplemented,
+        Some(solution) => {
+            let path =
+                input_dir.join(entry.year.to_string()).join(&entry.input);
+            let answer = crate::input::read(&path).and_then(|input| {
+                solution.solve(entry.part, &input, &Params::default())
+            });
+            match answer {
+                Ok(answer) if answer == entry.answer => Status::Pass,
+                Ok(answer) => Status::Regressed(answer),
+                Err(e) if crate::solution::is_unimplemented(&e) => {
+                    Status::Unimplemented
+                }
+                Err(e) => Status::Fail(e),
+            }
+        }
+    };
+
+    Verification { entry, status }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let manifest = super::Manifest::parse(
+            2023,
+            r#"# A comment.
+1 1 day01.txt 54450
+
+1 1 day01-alice.txt 55123
+9 2 day09.txt -3
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.entries.len(), 3);
+        assert_eq!(manifest.entries[0].year, 2023);
+        assert_eq!(
+            manifest.answer(1, 1, "day01-alice.txt"),
+            Some(&55123u64.into())
+        );
+        assert_eq!(manifest.answer(9, 2, "day09.txt"), Some(&(-3i64).into()));
+        assert_eq!(manifest.answer(1, 2, "day01.txt"), None);
+
+        assert!(super::Manifest::parse(2023, "1 1 day01.txt").is_err());
+        assert!(super::Manifest::parse(2023, "1 3 day01.txt 5").is_err());
+        assert!(super::Manifest::parse(
+            2023,
+            "1 1 day01.txt 5\n1 1 day01.txt 6"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let input_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+
+        // Every answer should be for a checked in input, which is encrypted.
+        for year in crate::solution::years() {
+            let manifest = super::Manifest::load(&input_dir, year).unwrap();
+            for entry in &manifest.entries {
+                let path = input_dir.join(year.to_string()).join(&entry.input);
+                assert!(
+                    crate::input::encrypted_path(&path).exists(),
+                    "{:?}",
+                    entry
+                );
+            }
+        }
+        assert_eq!(
+            super::Manifest::load(&input_dir, 2015).unwrap(),
+            super::Manifest::default()
+        );
+
+        // The rest runs day 1 on its input, so it needs the key.
+        crate::test_input!(2023, 1);
+
+        let manifest = super::Manifest::parse(
+            2023,
+            r#"1 1 day01.txt 54450
+1 2 day01.txt 1
+2 1 missing.txt 1
+26 1 day26.txt 1
+"#,
+        )
+        .unwrap();
+        let statuses = manifest
+            .entries
+            .iter()
+            .map(|entry| super::verify(entry, &input_dir).to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(statuses[0], "2023 day 01 part 1 day01.txt: pass");
+        assert_eq!(
+            statuses[1],
+            "2023 day 01 part 2 day01.txt: regressed: got 54265, expected 1"
+        );
+        assert!(
+            statuses[2].starts_with("2023 day 02 part 1 missing.txt: fail: ")
+        );
+        assert_eq!(statuses[3], "2023 day 26 part 1 day26.txt: unimplemented");
+    }
+}
@@ -0,0 +1,113 @@
+//! Named parameters that override the defaults of a puzzle.
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use anyhow::Result;
+
+/// Named parameters passed to a solution, for example with
+/// `--param multiplier=100` on the command line.
+///
+/// Each day that takes parameters reads them into its own `Options` struct,
+/// which documents the names and the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// Set a parameter.
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    /// Whether no parameters were set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Parse a `name=value` pair and set the parameter.
+    pub fn parse_pair(&mut self, pair: &str) -> Result<()> {
+        let (name, value) = pair.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("invalid parameter, expected name=value: {}", pair)
+        })?;
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("missing parameter name: {}", pair);
+        }
+
+        self.set(name, value.trim());
+
+        Ok(())
+    }
+
+    /// Get a parameter, or the default if it was not set.
+    pub fn get_or<T>(&self, name: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        match self.0.get(name) {
+            Some(value) => value.parse::<T>().map_err(|e| {
+                anyhow::anyhow!("invalid value for {}: {}: {}", name, value, e)
+            }),
+            None => Ok(default),
+        }
+    }
+
+    /// Make sure only the known parameters were set.
+    pub fn expect_only(&self, known: &[&str]) -> Result<()> {
+        for name in self.0.keys() {
+            if !known.contains(&name.as_str()) {
+                if known.is_empty() {
+                    anyhow::bail!("unknown parameter: {}", name);
+                }
+                anyhow::bail!(
+                    "unknown parameter: {}, expected one of: {}",
+                    name,
+                    known.join(", ")
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_params() {
+        let mut params = super::Params::default();
+        params.parse_pair("multiplier=100").unwrap();
+        params.parse_pair(" start = BBB ").unwrap();
+        assert!(params.parse_pair("multiplier").is_err());
+        assert!(params.parse_pair("=100").is_err());
+
+        assert_eq!(params.get_or("multiplier", 2u64).unwrap(), 100);
+        assert_eq!(params.get_or("unfold", 5usize).unwrap(), 5);
+        assert_eq!(
+            params.get_or("start", String::new()).unwrap(),
+            "BBB".to_string()
+        );
+        assert!(params.get_or("start", 0u64).is_err());
+
+        assert!(params.expect_only(&["multiplier", "start"]).is_ok());
+        assert!(params.expect_only(&["multiplier"]).is_err());
+        assert!(params.expect_only(&[]).is_err());
+
+        assert!(!params.is_empty());
+        assert_eq!(params.to_string(), "multiplier=100 start=BBB");
+    }
+}
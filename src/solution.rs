@@ -0,0 +1,265 @@
+//! A common interface over the solutions for every day.
+use std::{any::Any, fmt, panic::AssertUnwindSafe};
+
+use anyhow::Result;
+
+use crate::{Answer, Backend, Params};
+
+/// A solution for a day of Advent of Code.
+pub trait Solution: Sync {
+    /// The year of the puzzle.
+    fn year(&self) -> u32;
+
+    /// The day of the puzzle.
+    fn day(&self) -> u32;
+
+    /// The title of the puzzle.
+    fn title(&self) -> &'static str;
+
+    /// The backends the part can run on. The first one is the default.
+    fn backends(&self, _part: u32) -> &'static [Backend] {
+        &[Backend::Serial]
+    }
+
+    /// The names of the parameters the solution takes, see the `Options` in
+    /// the module for the day.
+    fn params(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Parse the input for the part without solving it, so benchmarks can
+    /// time parsing on its own, and return whether it did. The default does
+    /// nothing and returns `false`, for solutions that parse as they go.
+    fn parse(
+        &self,
+        _part: u32,
+        _input: &str,
+        _params: &Params,
+    ) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Solve part one of the puzzle on the backend, which is one of
+    /// `backends(1)`.
+    fn part_1(
+        &self,
+        input: &str,
+        params: &Params,
+        backend: Backend,
+    ) -> Result<Answer>;
+
+    /// Solve part two of the puzzle on the backend, which is one of
+    /// `backends(2)`.
+    fn part_2(
+        &self,
+        input: &str,
+        params: &Params,
+        backend: Backend,
+    ) -> Result<Answer>;
+
+    /// Solve the given part of the puzzle on the default backend.
+    fn solve(&self, part: u32, input: &str, params: &Params) -> Result<Answer> {
+        let backend =
+            self.backends(part).first().copied().ok_or_else(|| {
+                anyhow::anyhow!("no backends for part {}", part)
+            })?;
+
+        self.solve_on(part, backend, input, params)
+    }
+
+    /// Solve the given part of the puzzle on the backend, after checking the
+    /// part can run on it and the parameters are ones the solution takes.
+    fn solve_on(
+        &self,
+        part: u32,
+        backend: Backend,
+        input: &str,
+        params: &Params,
+    ) -> Result<Answer> {
+        if part != 1 && part != 2 {
+            anyhow::bail!("invalid part: {}", part);
+        }
+        if !self.backends(part).contains(&backend) {
+            anyhow::bail!(
+                "no {} backend for {} day {} part {}, expected one of: {}",
+                backend,
+                self.year(),
+                self.day(),
+                part,
+                self.backends(part)
+                    .iter()
+                    .map(|backend| backend.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        if !backend.is_available() {
+            anyhow::bail!("the {} backend needs the `cuda` feature", backend);
+        }
+        params.expect_only(self.params())?;
+
+        // Catch panics, so a `todo!()` in one part doesn't abort a run over
+        // every day.
+        std::panic::catch_unwind(AssertUnwindSafe(|| match part {
+            1 => self.part_1(input, params, backend),
+            _ => self.part_2(input, params, backend),
+        }))
+        .unwrap_or_else(|payload| {
+            if is_unimplemented_panic(payload.as_ref()) {
+                Err(Unimplemented.into())
+            } else {
+                Err(anyhow::anyhow!(
+                    "panicked: {}",
+                    panic_message(payload.as_ref()).unwrap_or("unknown panic")
+                ))
+            }
+        })
+    }
+}
+
+/// The error for a part of a puzzle that is not solved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unimplemented;
+
+impl fmt::Display for Unimplemented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not yet solved")
+    }
+}
+
+impl std::error::Error for Unimplemented {}
+
+/// Whether the error is from a part that is not solved yet.
+pub fn is_unimplemented(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<Unimplemented>().is_some()
+}
+
+/// Whether the panic is from a `todo!()` or an `unimplemented!()`.
+pub fn is_unimplemented_panic(payload: &(dyn Any + Send)) -> bool {
+    panic_message(payload).map_or(false, |message| {
+        message.starts_with("not yet implemented")
+            || message.starts_with("not implemented")
+    })
+}
+
+/// Get the message of a panic, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(|s| s.as_str()))
+}
+
+/// Find the solution for the year and day in the registry.
+pub fn find(year: u32, day: u32) -> Option<&'static dyn Solution> {
+    crate::SOLUTIONS
+        .iter()
+        .find(|solution| solution.year() == year && solution.day() == day)
+        .copied()
+}
+
+/// The years with solutions in the registry, in order.
+pub fn years() -> Vec<u32> {
+    let mut years = crate::SOLUTIONS
+        .iter()
+        .map(|solution| solution.year())
+        .collect::<Vec<_>>();
+    years.dedup();
+    years
+}
+
+/// The latest year with solutions, which is the default when no year is
+/// given.
+pub fn latest_year() -> Option<u32> {
+    years().last().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_registry() {
+        // Every solution should be registered once, in order of year and
+        // day.
+        let years = super::years();
+        assert!(years.windows(2).all(|years| years[0] < years[1]));
+        for year in years {
+            let days = crate::SOLUTIONS
+                .iter()
+                .filter(|solution| solution.year() == year)
+                .map(|solution| solution.day())
+                .collect::<Vec<_>>();
+            assert_eq!(days, (1..=days.len() as u32).collect::<Vec<_>>());
+        }
+
+        assert_eq!(super::find(2023, 7).unwrap().title(), "Camel Cards");
+        assert!(super::find(2023, 26).is_none());
+        assert!(super::find(2015, 7).is_none());
+        assert_eq!(super::latest_year(), Some(2023));
+
+        // Parts can only run on the backends they list.
+        let day07 = super::find(2023, 7).unwrap();
+        let params = crate::Params::default();
+        assert!(day07
+            .solve_on(1, crate::Backend::Cuda, "", &params)
+            .is_err());
+        assert!(day07.solve(3, "", &params).is_err());
+    }
+
+    /// A solution with parts that are not finished.
+    struct Unfinished;
+
+    impl super::Solution for Unfinished {
+        fn year(&self) -> u32 {
+            2023
+        }
+
+        fn day(&self) -> u32 {
+            26
+        }
+
+        fn title(&self) -> &'static str {
+            "Unfinished"
+        }
+
+        fn part_1(
+            &self,
+            input: &str,
+            _params: &crate::Params,
+            _backend: crate::Backend,
+        ) -> anyhow::Result<crate::Answer> {
+            match input {
+                "todo" => todo!(),
+                "error" => Err(super::Unimplemented.into()),
+                _ => panic!("bad input"),
+            }
+        }
+
+        fn part_2(
+            &self,
+            _input: &str,
+            _params: &crate::Params,
+            _backend: crate::Backend,
+        ) -> anyhow::Result<crate::Answer> {
+            unimplemented!("part two")
+        }
+    }
+
+    #[test]
+    fn test_unimplemented() {
+        use super::Solution;
+
+        let params = crate::Params::default();
+        for (part, input) in [(1, "todo"), (1, "error"), (2, "")] {
+            let e = Unfinished.solve(part, input, &params).unwrap_err();
+            assert!(super::is_unimplemented(&e), "{}", e);
+            assert_eq!(e.to_string(), "not yet solved");
+        }
+
+        // Other panics are errors too, but not unimplemented ones.
+        let e = Unfinished.solve(1, "other", &params).unwrap_err();
+        assert!(!super::is_unimplemented(&e));
+        assert_eq!(e.to_string(), "panicked: bad input");
+    }
+}
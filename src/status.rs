@@ -0,0 +1,494 @@
+//! The state of every day of a year, from what is in the repo: the inputs,
+//! the solutions and their kernels, the known answers and the benchmarks.
+use std::{fmt, path::Path, time::Duration};
+
+use anyhow::Result;
+
+use crate::{manifest, Answer, Manifest};
+
+/// The number of days in a year of Advent of Code.
+pub const DAYS: u32 = 25;
+
+/// How far along the solution for a part is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Implementation {
+    /// There is no module for the day.
+    Missing,
+    /// The part is still `todo!()`, or the unimplemented error from the
+    /// template.
+    Todo,
+    /// The part has a solution.
+    Done,
+}
+
+/// The state of a part of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartStatus {
+    /// How far along the solution is.
+    pub implementation: Implementation,
+    /// The known answer for the checked in input, from the manifest.
+    pub answer: Option<Answer>,
+    /// Whether the solution gets the known answer, once it is verified.
+    pub verified: Option<bool>,
+    /// The median time to solve the part in the latest benchmark, on the
+    /// fastest backend.
+    pub bench: Option<Duration>,
+}
+
+/// The state of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStatus {
+    /// The day of the puzzle.
+    pub day: u32,
+    /// Whether the input is checked in, encrypted or not.
+    pub input: bool,
+    /// Whether there is a GPU kernel for the day.
+    pub kernel: bool,
+    /// The parts of the day.
+    pub parts: [PartStatus; 2],
+    /// When the day was last benchmarked, in seconds since the epoch.
+    pub benched_at: Option<u64>,
+}
+
+/// The state of every day of a year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calendar {
+    /// The year of the puzzles.
+    pub year: u32,
+    /// Every day, in order.
+    pub days: Vec<DayStatus>,
+}
+
+impl Calendar {
+    /// Scan the repo for the state of every day of the year, with the
+    /// benchmarks from the CSV file `bench` writes, if there is one.
+    pub fn scan(repo_dir: &Path, year: u32, bench_csv: &Path) -> Result<Self> {
+        let input_dir = repo_dir.join("input");
+        let manifest = Manifest::load(&input_dir, year)?;
+        let benchmarks = read_benchmarks(bench_csv, year)?;
+        let module = format!("y{}", year);
+
+        let mut days = Vec::new();
+        for day in 1..=DAYS {
+            let name = format!("day{:02}.rs", day);
+            let input = format!("day{:02}.txt", day);
+            let input_path = input_dir.join(year.to_string()).join(&input);
+            let source_path = repo_dir.join("src").join(&module).join(&name);
+            let source = std::fs::read_to_string(&source_path).ok();
+
+            // Only the latest run of the day counts.
+            let runs = benchmarks.iter().filter(|run| run.day == day);
+            let benched_at = runs.clone().map(|run| run.timestamp).max();
+            let latest = runs
+                .filter(|run| Some(run.timestamp) == benched_at)
+                .collect::<Vec<_>>();
+
+            // A module that doesn't parse is an error, rather than a guess.
+            let in_source = |e: anyhow::Error| {
+                anyhow::anyhow!("{} in {}", e, source_path.display())
+            };
+            let implementations = match &source {
+                Some(source) => [
+                    implementation(source, 1).map_err(in_source)?,
+                    implementation(source, 2).map_err(in_source)?,
+                ],
+                None => [Implementation::Missing; 2],
+            };
+
+            let part = |part: u32| PartStatus {
+                implementation: implementations[part as usize - 1],
+                answer: manifest.answer(day, part, &input).cloned(),
+                verified: None,
+                bench: latest
+                    .iter()
+                    .filter(|run| run.part == part)
+                    .map(|run| run.solve_median)
+                    .min(),
+            };
+
+            days.push(DayStatus {
+                day,
+                input: input_path.exists()
+                    || crate::input::encrypted_path(&input_path).exists(),
+                kernel: repo_dir
+                    .join("gpu")
+                    .join("src")
+                    .join(&module)
+                    .join(&name)
+                    .exists(),
+                parts: [part(1), part(2)],
+                benched_at,
+            });
+        }
+
+        Ok(Calendar { year, days })
+    }
+
+    /// Run the solutions for the parts with a known answer, and record
+    /// whether they still get it.
+    pub fn verify(&mut self, input_dir: &Path) -> Result<()> {
+        let manifest = Manifest::load(input_dir, self.year)?;
+        for status in &mut self.days {
+            let input = format!("day{:02}.txt", status.day);
+            for entry in manifest
+                .entries
+                .iter()
+                .filter(|entry| entry.day == status.day && entry.input == input)
+            {
+                let part = &mut status.parts[entry.part as usize - 1];
+                if part.implementation == Implementation::Done {
+                    let verification = manifest::verify(entry, input_dir);
+                    part.verified = Some(matches!(
+                        verification.status,
+                        manifest::Status::Pass
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of stars, one for each part with a known answer.
+    pub fn stars(&self) -> usize {
+        self.days
+            .iter()
+            .flat_map(|day| &day.parts)
+            .filter(|part| part.answer.is_some())
+            .count()
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Advent of Code {}: {} stars", self.year, self.stars())?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>3}  {:<5}  {:<8}  {:<8}  {:<3}  Benchmarked",
+            "Day", "Input", "Part 1", "Part 2", "GPU"
+        )?;
+        for day in &self.days {
+            let yes_no = |yes: bool| if yes { "yes" } else { "-" };
+            let bench = match day.benched_at {
+                Some(timestamp) => format!(
+                    "{}: {}",
+                    date(timestamp),
+                    day.parts
+                        .iter()
+                        .map(|part| match part.bench {
+                            Some(time) => format!("{:.1?}", time),
+                            None => "-".to_string(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                None => "-".to_string(),
+            };
+            let line = format!(
+                "{:>3}  {:<5}  {:<8}  {:<8}  {:<3}  {}",
+                day.day,
+                yes_no(day.input),
+                part_state(&day.parts[0]),
+                part_state(&day.parts[1]),
+                yes_no(day.kernel),
+                bench
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How far along the part is, in a word.
+fn part_state(part: &PartStatus) -> &'static str {
+    match (part.implementation, &part.answer, part.verified) {
+        (Implementation::Missing, _, _) => "-",
+        (Implementation::Todo, _, _) => "todo",
+        (Implementation::Done, None, _) => "solved",
+        (Implementation::Done, Some(_), None) => "answered",
+        (Implementation::Done, Some(_), Some(true)) => "verified",
+        (Implementation::Done, Some(_), Some(false)) => "wrong",
+    }
+}
+
+/// Find how far along the solve function for the part is in the source of
+/// the module for the day, by parsing it. The part is still todo if there is
+/// no solve function for it, or its body uses `todo!()`, `unimplemented!()`
+/// or the `Unimplemented` error anywhere.
+pub fn implementation(source: &str, part: u32) -> Result<Implementation> {
+    let file = syn::parse_file(source)
+        .map_err(|e| anyhow::anyhow!("Failed to parse the day: {}", e))?;
+
+    let name = format!("solve_part_{}", part);
+    let function = file.items.iter().find_map(|item| match item {
+        syn::Item::Fn(function) if function.sig.ident == name => Some(function),
+        _ => None,
+    });
+
+    let mut stub = Stub::default();
+    match function {
+        Some(function) => syn::visit::visit_block(&mut stub, &function.block),
+        None => return Ok(Implementation::Todo),
+    }
+
+    Ok(if stub.found {
+        Implementation::Todo
+    } else {
+        Implementation::Done
+    })
+}
+
+/// Looks for what the template and unfinished code leave in a solve
+/// function.
+#[derive(Debug, Default)]
+struct Stub {
+    /// Whether a `todo!()`, `unimplemented!()` or `Unimplemented` was found.
+    found: bool,
+}
+
+impl<'ast> syn::visit::Visit<'ast> for Stub {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if let Some(last) = mac.path.segments.last() {
+            if last.ident == "todo" || last.ident == "unimplemented" {
+                self.found = true;
+            }
+        }
+        syn::visit::visit_macro(self, mac);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if let Some(last) = path.segments.last() {
+            if last.ident == "Unimplemented" {
+                self.found = true;
+            }
+        }
+        syn::visit::visit_path(self, path);
+    }
+}
+
+/// A row of the benchmarks for the year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Benchmark {
+    /// When the benchmark ran, in seconds since the epoch.
+    timestamp: u64,
+    /// The day of the puzzle.
+    day: u32,
+    /// The part of the puzzle.
+    part: u32,
+    /// The median time to solve the part.
+    solve_median: Duration,
+}
+
+/// Read the benchmarks for the year from the CSV file, if there is one.
+fn read_benchmarks(path: &Path, year: u32) -> Result<Vec<Benchmark>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let csv = std::fs::read_to_string(path).map_err(|e| {
+        anyhow::anyhow!("Failed to read {}: {}", path.display(), e)
+    })?;
+
+    let mut benchmarks = Vec::new();
+    for (i, line) in csv.lines().enumerate().skip(1) {
+        // The answer is last and the only field that can be quoted, so the
+        // fields before it are never split wrong.
+        let fields = line.split(',').collect::<Vec<_>>();
+        let field = |n: usize| {
+            fields.get(n).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Missing field on line {} of {}",
+                    i + 1,
+                    path.display()
+                )
+            })
+        };
+        if field(1)?.parse::<u32>()? != year {
+            continue;
+        }
+        benchmarks.push(Benchmark {
+            timestamp: field(0)?.parse()?,
+            day: field(2)?.parse()?,
+            part: field(3)?.parse()?,
+            solve_median: Duration::from_nanos(field(9)?.parse()?),
+        });
+    }
+
+    Ok(benchmarks)
+}
+
+/// The date of the timestamp, in seconds since the epoch, as `YYYY-MM-DD`.
+fn date(timestamp: u64) -> String {
+    // Count the days from 0000-03-01, so the leap day is at the end of the
+    // year.
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::Implementation;
+
+    /// A fake repo with two days, in a temporary directory.
+    struct Repo {
+        temp: TempDir,
+    }
+
+    impl Repo {
+        fn new() -> Self {
+            let temp = TempDir::new().unwrap();
+            let files = [
+                (
+                    "src/y2023/day01.rs",
+                    "pub fn solve_part_1(input: &str) -> Result<u32> {\n    \
+                     Ok(input.len() as u32)\n}\n\n\
+                     pub fn solve_part_2(input: &str) -> Result<u32> {\n    \
+                     Ok(0)\n}\n",
+                ),
+                (
+                    "src/y2023/day02.rs",
+                    "pub fn solve_part_1(input: &str) -> Result<u32> {\n    \
+                     Ok(1)\n}\n\n\
+                     pub fn solve_part_2(_input: &str) -> Result<u32> {\n    \
+                     todo!()\n}\n",
+                ),
+                ("gpu/src/y2023/day02.rs", ""),
+                ("input/2023/day01.txt.enc", ""),
+                ("input/2023/day02.txt", ""),
+                (
+                    "input/2023/answers.txt",
+                    "1 1 day01.txt 6\n2 1 day01.txt 5\n",
+                ),
+                (
+                    "bench.csv",
+                    "timestamp,year,day,part,backend,samples,\
+                     parse_median_ns,parse_min_ns,parse_stddev_ns,\
+                     solve_median_ns,solve_min_ns,solve_stddev_ns,answer\n\
+                     1701388800,2023,1,1,serial,10,0,0,0,5000,0,0,6\n\
+                     1702166400,2023,1,1,serial,10,0,0,0,3000,0,0,6\n\
+                     1702166400,2023,1,1,rayon,10,0,0,0,2000,0,0,6\n\
+                     1702166400,2022,2,1,serial,10,0,0,0,2000,0,0,6\n",
+                ),
+            ];
+            for (path, contents) in files {
+                let path = temp.path().join(path);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, contents).unwrap();
+            }
+
+            Repo { temp }
+        }
+
+        /// The root of the repo.
+        fn dir(&self) -> &Path {
+            self.temp.path()
+        }
+    }
+
+    #[test]
+    fn test_scan() {
+        let repo = Repo::new();
+        let calendar = super::Calendar::scan(
+            repo.dir(),
+            2023,
+            &repo.dir().join("bench.csv"),
+        )
+        .unwrap();
+        assert_eq!(calendar.days.len(), 25);
+        assert_eq!(calendar.stars(), 1);
+
+        let day01 = &calendar.days[0];
+        assert!(day01.input && !day01.kernel);
+        assert_eq!(day01.parts[0].implementation, Implementation::Done);
+        assert_eq!(day01.parts[0].answer, Some(6u64.into()));
+        assert_eq!(day01.parts[0].bench, Some(Duration::from_micros(2)));
+        assert_eq!(day01.parts[1].answer, None);
+        assert_eq!(day01.parts[1].bench, None);
+        assert_eq!(day01.benched_at, Some(1702166400));
+
+        let day02 = &calendar.days[1];
+        assert!(day02.input && day02.kernel);
+        assert_eq!(day02.parts[1].implementation, Implementation::Todo);
+        assert_eq!(day02.benched_at, None);
+        assert_eq!(
+            calendar.days[2].parts[0].implementation,
+            Implementation::Missing
+        );
+
+        let table = calendar.to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Advent of Code 2023: 1 stars");
+        assert_eq!(
+            lines[2],
+            "Day  Input  Part 1    Part 2    GPU  Benchmarked"
+        );
+        assert_eq!(
+            lines[3],
+            "  1  yes    answered  solved    -    2023-12-10: 2.0µs, -"
+        );
+        assert_eq!(lines[4], "  2  yes    solved    todo      yes  -");
+        assert_eq!(lines[5], "  3  -      -         -         -    -");
+        assert_eq!(lines.len(), 28);
+    }
+
+    #[test]
+    fn test_implementation() {
+        let source = "pub fn solve_part_1(input: &str) -> Result<u32> {\n    \
+                      Err(crate::Unimplemented.into())\n}\n\n\
+                      pub fn solve_part_2(input: &str) -> Result<u32> {\n    \
+                      // Not Unimplemented any more.\n    \
+                      Ok(0)\n}\n";
+        let implementation =
+            |source, part| super::implementation(source, part).unwrap();
+        assert_eq!(implementation(source, 1), Implementation::Todo);
+        assert_eq!(implementation(source, 2), Implementation::Done);
+        assert_eq!(implementation("", 1), Implementation::Todo);
+
+        // A stub counts wherever it is in the function, however it is
+        // formatted.
+        let source = "pub fn solve_part_1(input: &str) -> Result<u32> {\n    \
+                      let lines = input.lines().count();\n    \
+                      if lines > 0 { std::todo!() } else { Ok(0) }\n}\n";
+        assert_eq!(implementation(source, 1), Implementation::Todo);
+
+        assert!(super::implementation("pub fn solve_part_1(", 1).is_err());
+
+        // The days of this repo, where part two of day 11 is solved in a
+        // helper and part two of day 13 isn't solved yet.
+        let day = |day: &str| {
+            std::fs::read_to_string(
+                crate::input::repo_dir().join("src").join("y2023").join(day),
+            )
+            .unwrap()
+        };
+        let (day11, day13) = (day("day11.rs"), day("day13.rs"));
+        assert_eq!(implementation(&day11, 2), Implementation::Done);
+        assert_eq!(implementation(&day13, 1), Implementation::Done);
+        assert_eq!(implementation(&day13, 2), Implementation::Todo);
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(super::date(0), "1970-01-01");
+        assert_eq!(super::date(951_782_400), "2000-02-29");
+        assert_eq!(super::date(1_701_388_800), "2023-12-01");
+    }
+}
@@ -178,6 +178,41 @@ pub fn solve_part_2(input: &str) -> Result<u32> {
     Ok(nums.iter().sum())
 }
 
+/// The solution for day 01.
+pub struct Day01;
+
+impl crate::Solution for Day01 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -185,14 +220,14 @@ mod tests {
     #[test]
     fn test_solve_part_1() {
         // Load the file.
-        let input = include_str!("../input/day01.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 54450);
+        let input = crate::test_input!(2023, 1);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 54450);
     }
 
     #[test]
     fn test_solve_part_2() {
         // Load the file.
-        let input = include_str!("../input/day01.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 54265);
+        let input = crate::test_input!(2023, 1);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 54265);
     }
 }
@@ -187,6 +187,41 @@ pub fn solve_part_2(input: &str) -> Result<u32> {
     Ok(nums.iter().sum())
 }
 
+/// The solution for day 02.
+pub struct Day02;
+
+impl crate::Solution for Day02 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -194,14 +229,14 @@ mod tests {
     #[test]
     fn test_solve_part_1() {
         // Load the file.
-        let input = include_str!("../input/day02.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 1853);
+        let input = crate::test_input!(2023, 2);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 1853);
     }
 
     #[test]
     fn test_solve_part_2() {
         // Load the file.
-        let input = include_str!("../input/day02.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 72706);
+        let input = crate::test_input!(2023, 2);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 72706);
     }
 }
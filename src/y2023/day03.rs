@@ -0,0 +1,298 @@
+//!  Day 03: Gear Ratios
+use anyhow::Result;
+
+use crate::{grid::Position, Grid};
+
+/// A cell of the engine schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    /// A digit of a number.
+    Digit(u32),
+    /// A symbol, which is anything but a digit or a period.
+    Symbol(char),
+    /// A period.
+    Empty,
+}
+
+impl Cell {
+    /// Parse a cell.
+    fn parse(c: char) -> Result<Self> {
+        Ok(match c {
+            '.' => Self::Empty,
+            _ => match c.to_digit(10) {
+                Some(digit) => Self::Digit(digit),
+                None => Self::Symbol(c),
+            },
+        })
+    }
+}
+
+/// The engine schematic, with the numbers in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Schematic {
+    /// The cells.
+    grid: Grid<Cell>,
+    /// The numbers, in reading order.
+    numbers: Vec<u32>,
+    /// The number each cell is a digit of, as its index in `numbers`.
+    number_at: Grid<Option<usize>>,
+}
+
+impl Schematic {
+    /// Parse an engine schematic.
+    fn parse(input: &str) -> Result<Self> {
+        let grid = Grid::parse(input, Cell::parse)?;
+
+        let mut numbers: Vec<u32> = Vec::new();
+        let mut number_at: Grid<Option<usize>> =
+            Grid::filled(grid.width(), grid.height(), None);
+        for ((x, y), cell) in grid.iter() {
+            if let Cell::Digit(digit) = cell {
+                // A digit carries on the number to its left, if there is one.
+                let previous = x.checked_sub(1).and_then(|x| number_at[(x, y)]);
+                let index = match previous {
+                    Some(index) => {
+                        numbers[index] = numbers[index]
+                            .checked_mul(10)
+                            .and_then(|number| number.checked_add(*digit))
+                            .ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "number too large on line {}",
+                                    y + 1
+                                )
+                            })?;
+                        index
+                    }
+                    None => {
+                        numbers.push(*digit);
+                        numbers.len() - 1
+                    }
+                };
+                number_at[(x, y)] = Some(index);
+            }
+        }
+
+        Ok(Schematic {
+            grid,
+            numbers,
+            number_at,
+        })
+    }
+
+    /// The numbers next to the position, including diagonally, each once.
+    fn adjacent_numbers(&self, position: Position) -> Vec<usize> {
+        let mut adjacent = Vec::new();
+        for neighbour in self.grid.neighbours8(position) {
+            if let Some(number) = self.number_at[neighbour] {
+                if !adjacent.contains(&number) {
+                    adjacent.push(number);
+                }
+            }
+        }
+
+        adjacent
+    }
+}
+
+/// You and the Elf eventually reach a gondola lift (<https://en.wikipedia.org/wiki/Gondola_lift>) station; he says the gondola lift will take you up to the *water source*, but this is as far as he can bring you. You go inside.
+///
+/// It doesn't take long to find the gondolas, but there seems to be a problem:
+/// they're not moving.
+///
+/// "Aaah!"
+///
+/// You turn around to see a slightly-greasy Elf with a wrench and a look of
+/// surprise. "Sorry, I wasn't expecting anyone! The gondola lift isn't working
+/// right now; it'll still be a while before I can fix it." You offer to help.
+///
+/// The engineer explains that an engine part seems to be missing from the
+/// engine, but nobody can figure out which one. If you can *add up all the part
+/// numbers* in the engine schematic, it should be easy to work out which part
+/// is missing.
+///
+/// The engine schematic (your puzzle input) consists of a visual representation
+/// of the engine. There are lots of numbers and symbols you don't really
+/// understand, but apparently *any number adjacent to a symbol*, even
+/// diagonally, is a "part number" and should be included in your sum. (Periods
+/// (`.`) do not count as a symbol.)
+///
+/// Here is an example engine schematic:
+///
+/// ```ignore
+/// 467..114..
+/// ...*......
+/// ..35..633.
+/// ......#...
+/// 617*......
+/// .....+.58.
+/// ..592.....
+/// ......755.
+/// ...$.*....
+/// .664.598..
+/// ```
+///
+/// In this schematic, two numbers are *not* part numbers because they are not
+/// adjacent to a symbol: `114` (top right) and `58` (middle right). Every other
+/// number is adjacent to a symbol and so *is* a part number; their sum is
+/// `*4361*`.
+///
+/// Of course, the actual engine schematic is much larger. *What is the sum of
+/// all of the part numbers in the engine schematic?*
+pub fn solve_part_1(input: &str) -> Result<u32> {
+    let schematic = Schematic::parse(input)?;
+
+    // A number is a part number if it is next to any symbol.
+    let mut is_part_number = vec![false; schematic.numbers.len()];
+    for (position, cell) in schematic.grid.iter() {
+        if let Cell::Symbol(_) = cell {
+            for number in schematic.adjacent_numbers(position) {
+                is_part_number[number] = true;
+            }
+        }
+    }
+
+    Ok(schematic
+        .numbers
+        .iter()
+        .zip(is_part_number)
+        .filter(|(_, is_part_number)| *is_part_number)
+        .map(|(number, _)| number)
+        .sum())
+}
+
+/// The engineer finds the missing part and installs it in the engine! As the
+/// engine springs to life, you jump in the closest gondola, finally ready to
+/// ascend to the water source.
+///
+/// You don't seem to be going very fast, though. Maybe something is still
+/// wrong? Fortunately, the gondola has a phone labeled "help", so you pick it
+/// up and the engineer answers.
+///
+/// Before you can explain the situation, she suggests that you look out the
+/// window. There stands the engineer, holding a phone in one hand and waving
+/// with the other. You're going so slowly that you haven't even left the
+/// station. You exit the gondola.
+///
+/// The missing part wasn't the only issue - one of the gears in the engine is
+/// wrong. A *gear* is any `*` symbol that is adjacent to *exactly two part
+/// numbers*. Its *gear ratio* is the result of multiplying those two numbers
+/// together.
+///
+/// This time, you need to find the gear ratio of every gear and add them all up
+/// so that the engineer can figure out which gear needs to be replaced.
+///
+/// Consider the same engine schematic again:
+///
+/// ```ignore
+/// 467..114..
+/// ...*......
+/// ..35..633.
+/// ......#...
+/// 617*......
+/// .....+.58.
+/// ..592.....
+/// ......755.
+/// ...$.*....
+/// .664.598..
+/// ```
+///
+/// In this schematic, there are *two* gears. The first is in the top left; it
+/// has part numbers `467` and `35`, so its gear ratio is `16345`. The second
+/// gear is in the lower right; its gear ratio is `451490`. (The `*` adjacent to
+/// `617` is *not* a gear because it is only adjacent to one part number.)
+/// Adding up all of the gear ratios produces `*467835*`.
+///
+/// *What is the sum of all of the gear ratios in your engine schematic?*
+pub fn solve_part_2(input: &str) -> Result<u32> {
+    let schematic = Schematic::parse(input)?;
+
+    let mut gear_ratios: Vec<u32> = Vec::new();
+    for (position, cell) in schematic.grid.iter() {
+        if *cell != Cell::Symbol('*') {
+            continue;
+        }
+
+        // Check if two different numbers are adjacent to the symbol.
+        let adjacent_numbers = schematic.adjacent_numbers(position);
+        if adjacent_numbers.len() == 2 {
+            gear_ratios.push(
+                adjacent_numbers
+                    .iter()
+                    .map(|number| schematic.numbers[*number])
+                    .product(),
+            );
+        }
+    }
+
+    Ok(gear_ratios.iter().sum())
+}
+
+/// The solution for day 03.
+pub struct Day03;
+
+impl crate::Solution for Day03 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    /// The example from the puzzle.
+    const EXAMPLE: &str = r#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598.."#;
+
+    #[test]
+    fn test_solve_part_1() {
+        assert_eq!(super::solve_part_1(EXAMPLE).unwrap(), 4361);
+
+        // Load the file.
+        let input = crate::test_input!(2023, 3);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 535351);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        assert_eq!(super::solve_part_2(EXAMPLE).unwrap(), 467835);
+
+        // Load the file.
+        let input = crate::test_input!(2023, 3);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 87287096);
+    }
+}
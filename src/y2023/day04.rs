@@ -247,6 +247,54 @@ pub fn solve_part_2(input: &str) -> Result<u32> {
     Ok(cards)
 }
 
+/// The solution for day 04.
+pub struct Day04;
+
+impl crate::Solution for Day04 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn parse(
+        &self,
+        _part: u32,
+        input: &str,
+        _params: &crate::Params,
+    ) -> Result<bool> {
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            parse_scratch_card(line)?;
+        }
+
+        Ok(true)
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -254,14 +302,14 @@ mod tests {
     #[test]
     fn test_solve_part_1() {
         // Load the file.
-        let input = include_str!("../input/day04.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 21485);
+        let input = crate::test_input!(2023, 4);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 21485);
     }
 
     #[test]
     fn test_solve_part_2() {
         // Load the file.
-        let input = include_str!("../input/day04.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 11024379);
+        let input = crate::test_input!(2023, 4);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 11024379);
     }
 }
@@ -424,6 +424,55 @@ pub fn solve_part_2(input: &str) -> Result<u64> {
     Ok(lowest_location)
 }
 
+/// The solution for day 05.
+pub struct Day05;
+
+impl crate::Solution for Day05 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn backends(&self, _part: u32) -> &'static [crate::Backend] {
+        &[crate::Backend::Rayon]
+    }
+
+    fn parse(
+        &self,
+        _part: u32,
+        input: &str,
+        _params: &crate::Params,
+    ) -> Result<bool> {
+        parse_almanac(input)?;
+        Ok(true)
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -431,14 +480,14 @@ mod tests {
     #[test]
     fn test_solve_part_1() {
         // Load the file.
-        let input = include_str!("../input/day05.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 214922730);
+        let input = crate::test_input!(2023, 5);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 214922730);
     }
 
     #[test]
     fn test_solve_part_2() {
         // Load the file.
-        let input = include_str!("../input/day05.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 148041808);
+        let input = crate::test_input!(2023, 5);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 148041808);
     }
 }
@@ -205,6 +205,56 @@ pub fn solve_part_2(input: &str) -> Result<u64> {
     Ok(winning_times.len() as u64)
 }
 
+/// The solution for day 06.
+pub struct Day06;
+
+impl crate::Solution for Day06 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Wait For It"
+    }
+
+    fn parse(
+        &self,
+        part: u32,
+        input: &str,
+        _params: &crate::Params,
+    ) -> Result<bool> {
+        if part == 1 {
+            parse_races(input)?;
+        } else {
+            parse_race(input)?;
+        }
+
+        Ok(true)
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -212,14 +262,14 @@ mod tests {
     #[test]
     fn test_solve_part_1() {
         // Load the file.
-        let input = include_str!("../input/day06.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 440000);
+        let input = crate::test_input!(2023, 6);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 440000);
     }
 
     #[test]
     fn test_solve_part_2() {
         // Load the file.
-        let input = include_str!("../input/day06.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 26187338);
+        let input = crate::test_input!(2023, 6);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 26187338);
     }
 }
@@ -524,6 +524,58 @@ pub fn solve_part_2(input: &str) -> Result<u32> {
     Ok(winnings)
 }
 
+/// The solution for day 07.
+pub struct Day07;
+
+impl crate::Solution for Day07 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Camel Cards"
+    }
+
+    fn parse(
+        &self,
+        part: u32,
+        input: &str,
+        _params: &crate::Params,
+    ) -> Result<bool> {
+        for line in input.lines() {
+            if part == 1 {
+                parse_camel_card_hand(line)?;
+            } else {
+                parse_camel_card_hand_with_joker(line)?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -531,8 +583,8 @@ mod tests {
     #[test]
     fn test_solve_part_1() {
         // Load the file.
-        let input = include_str!("../input/day07.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 246424613);
+        let input = crate::test_input!(2023, 7);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 246424613);
     }
 
     #[test]
@@ -546,7 +598,7 @@ QQQJA 483"#;
         assert_eq!(super::solve_part_2(input).unwrap(), 5905);
 
         // Load the file.
-        let input = include_str!("../input/day07.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 248256639);
+        let input = crate::test_input!(2023, 7);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 248256639);
     }
 }
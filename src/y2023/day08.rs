@@ -1,9 +1,57 @@
 //!  Day 08: Haunted Wasteland
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::Result;
 use rayon::prelude::*;
 
+use crate::{Backend, Params};
+
+/// Options for the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The node part one starts at. Defaults to `AAA`.
+    pub start: [char; 3],
+    /// The node part one has to reach. Defaults to `ZZZ`.
+    pub goal: [char; 3],
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            start: ['A', 'A', 'A'],
+            goal: ['Z', 'Z', 'Z'],
+        }
+    }
+}
+
+impl Options {
+    /// The names of the parameters the options are read from.
+    pub const PARAMS: &'static [&'static str] = &["start", "goal"];
+
+    /// Read the options from the parameters, using the defaults for any that
+    /// are not set.
+    pub fn from_params(params: &Params) -> Result<Self> {
+        let default = Options::default();
+        let start =
+            params.get_or::<String>("start", default.start.iter().collect())?;
+        let goal =
+            params.get_or::<String>("goal", default.goal.iter().collect())?;
+        Ok(Options {
+            start: parse_label(&start)?,
+            goal: parse_label(&goal)?,
+        })
+    }
+}
+
+/// Parse a three character node label.
+fn parse_label(s: &str) -> Result<[char; 3]> {
+    s.trim()
+        .chars()
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("invalid label: {}", s))
+}
+
 /// The direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -39,6 +87,11 @@ impl InstructionMap {
         })
     }
 
+    /// Whether the label is in the map.
+    pub fn contains(&self, instruction: &[char; 3]) -> bool {
+        self.0.contains_key(instruction)
+    }
+
     /// Get the next left instruction.
     pub fn get_left(&self, instruction: &[char; 3]) -> Result<[char; 3]> {
         Ok(self.get(instruction)?.0)
@@ -215,12 +268,40 @@ fn calculate_lcm(cycle_lengths: Vec<u64>) -> u64 {
 /// Starting at `AAA`, follow the left/right instructions. *How many steps are
 /// required to reach `ZZZ`?*
 pub fn solve_part_1(input: &str) -> Result<u64> {
+    solve_part_1_with(input, &Options::default())
+}
+
+/// Solve part one, walking between the nodes in the options.
+pub fn solve_part_1_with(input: &str, options: &Options) -> Result<u64> {
     let (directions, instructions) = parse_directions_and_instructions(input)?;
 
-    // Now solve the puzzle.
-    let mut current_instruction = ['A', 'A', 'A'];
+    if directions.is_empty() {
+        anyhow::bail!("missing direction");
+    }
+    for (name, label) in [("start", options.start), ("goal", options.goal)] {
+        if !instructions.contains(&label) {
+            anyhow::bail!(
+                "the {} node {} is not in the map",
+                name,
+                label.iter().collect::<String>()
+            );
+        }
+    }
+
+    // Now solve the puzzle, stopping if the walk comes back to a node at the
+    // same point in the directions, since it would go round forever.
+    let mut seen = BTreeSet::new();
+    let mut current_instruction = options.start;
     let mut steps = 0;
-    while current_instruction != ['Z', 'Z', 'Z'] {
+    while current_instruction != options.goal {
+        if !seen.insert((current_instruction, steps % directions.len())) {
+            anyhow::bail!(
+                "the goal node {} can't be reached from {}",
+                options.goal.iter().collect::<String>(),
+                options.start.iter().collect::<String>()
+            );
+        }
+
         // Get the direction.
         let direction = if steps >= directions.len() {
             // Get the next instruction modulo the number of instructions.
@@ -305,12 +386,38 @@ pub fn solve_part_2(input: &str) -> Result<u64> {
     let (directions, instructions) = parse_directions_and_instructions(input)?;
 
     // Find all the instructions that end in `A`.
-    let mut current_instructions = instructions
+    let current_instructions = instructions
         .0
         .par_iter()
         .filter(|(k, (_, _))| k[2] == 'A')
         .map(|(k, _)| *k)
         .collect::<Vec<_>>();
+
+    walk_to_goals(&directions, &instructions, current_instructions)
+}
+
+/// Solve part two on a single thread.
+pub fn solve_part_2_serial(input: &str) -> Result<u64> {
+    let (directions, instructions) = parse_directions_and_instructions(input)?;
+
+    // Find all the instructions that end in `A`.
+    let current_instructions = instructions
+        .0
+        .iter()
+        .filter(|(k, (_, _))| k[2] == 'A')
+        .map(|(k, _)| *k)
+        .collect::<Vec<_>>();
+
+    walk_to_goals(&directions, &instructions, current_instructions)
+}
+
+/// Walk from every starting instruction at the same time, and return the
+/// number of steps until they are all on instructions that end in `Z`.
+fn walk_to_goals(
+    directions: &[Direction],
+    instructions: &InstructionMap,
+    mut current_instructions: Vec<[char; 3]>,
+) -> Result<u64> {
     let mut steps = 0;
     let mut cycle_lengths: BTreeMap<[char; 3], u64> = BTreeMap::new();
     while cycle_lengths.len() != current_instructions.len() {
@@ -347,15 +454,118 @@ pub fn solve_part_2(input: &str) -> Result<u64> {
     Ok(calculate_lcm(cycle_lengths.values().copied().collect()))
 }
 
+/// The solution for day 08.
+pub struct Day08;
+
+impl crate::Solution for Day08 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Haunted Wasteland"
+    }
+
+    fn backends(&self, part: u32) -> &'static [Backend] {
+        match part {
+            // Part two was written with rayon first, then ported to the GPU.
+            2 => &[
+                Backend::Rayon,
+                Backend::Serial,
+                Backend::Cuda,
+                Backend::Emulated,
+            ],
+            _ => &[Backend::Serial],
+        }
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        Options::PARAMS
+    }
+
+    fn parse(&self, _part: u32, input: &str, _params: &Params) -> Result<bool> {
+        parse_directions_and_instructions(input)?;
+        Ok(true)
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        params: &Params,
+        _backend: Backend,
+    ) -> Result<crate::Answer> {
+        let options = Options::from_params(params)?;
+        Ok(solve_part_1_with(input, &options)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &Params,
+        backend: Backend,
+    ) -> Result<crate::Answer> {
+        let steps = match backend {
+            Backend::Serial => solve_part_2_serial(input)?,
+            Backend::Rayon => solve_part_2(input)?,
+            #[cfg(feature = "cuda")]
+            Backend::Cuda => crate::y2023::gpu_day08::solve_part_2(input)?,
+            #[cfg(not(feature = "cuda"))]
+            Backend::Cuda => {
+                anyhow::bail!("the cuda backend needs the `cuda` feature")
+            }
+            Backend::Emulated => {
+                crate::y2023::gpu_day08::solve_part_2_emulated(input)?
+            }
+        };
+
+        Ok(steps.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
 
     #[test]
     fn test_solve_part_1() {
+        let input = r#"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)"#;
+        assert_eq!(super::solve_part_1(input).unwrap(), 6);
+        // Walk the same map backwards from a different start and goal.
+        let options = super::Options {
+            start: ['B', 'B', 'B'],
+            goal: ['A', 'A', 'A'],
+        };
+        assert_eq!(super::solve_part_1_with(input, &options).unwrap(), 1);
+
+        // A node that isn't in the map, or can't be reached, is an error
+        // rather than a walk that never ends.
+        let options = super::Options {
+            start: ['C', 'C', 'C'],
+            goal: ['Z', 'Z', 'Z'],
+        };
+        let e = super::solve_part_1_with(input, &options).unwrap_err();
+        assert_eq!(e.to_string(), "the start node CCC is not in the map");
+        let options = super::Options {
+            start: ['Z', 'Z', 'Z'],
+            goal: ['A', 'A', 'A'],
+        };
+        let e = super::solve_part_1_with(input, &options).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "the goal node AAA can't be reached from ZZZ"
+        );
+
         // Load the file.
-        let input = include_str!("../input/day08.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 16043);
+        let input = crate::test_input!(2023, 8);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 16043);
     }
 
     #[test]
@@ -371,9 +581,35 @@ mod tests {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)"#;
         assert_eq!(super::solve_part_2(input).unwrap(), 6);
+        assert_eq!(super::solve_part_2_serial(input).unwrap(), 6);
 
         // Load the file.
-        let input = include_str!("../input/day08.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 15726453850399);
+        let input = crate::test_input!(2023, 8);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 15726453850399);
+        assert_eq!(super::solve_part_2_serial(&input).unwrap(), 15726453850399);
+    }
+
+    #[test]
+    fn test_cross_check_part_2() {
+        // The backends that run by default have to get through the real
+        // input, which leaves out the emulator.
+        let input = crate::test_input!(2023, 8);
+        let check = crate::CrossCheck::run(
+            &super::Day08,
+            2,
+            &input,
+            &crate::Params::default(),
+            None,
+        )
+        .unwrap();
+        assert!(check.agrees(), "{}", check);
+        assert!(check
+            .results
+            .iter()
+            .all(|(backend, _)| *backend != crate::Backend::Emulated));
+        assert_eq!(
+            check.results[0].1.as_ref().unwrap(),
+            &crate::Answer::from(15726453850399u64)
+        );
     }
 }
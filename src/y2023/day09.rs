@@ -255,6 +255,41 @@ pub fn solve_part_2(input: &str) -> Result<i32> {
     Ok(first_values.iter().sum::<i32>())
 }
 
+/// The solution for day 09.
+pub struct Day09;
+
+impl crate::Solution for Day09 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Mirage Maintenance"
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -267,8 +302,8 @@ mod tests {
         assert_eq!(super::solve_part_1(input).unwrap(), 114);
 
         // Load the file.
-        let input = include_str!("../input/day09.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 1969958987);
+        let input = crate::test_input!(2023, 9);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 1969958987);
     }
 
     #[test]
@@ -279,7 +314,7 @@ mod tests {
         assert_eq!(super::solve_part_2(input).unwrap(), 2);
 
         // Load the file.
-        let input = include_str!("../input/day09.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 1068);
+        let input = crate::test_input!(2023, 9);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 1068);
     }
 }
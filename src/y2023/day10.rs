@@ -1,6 +1,8 @@
 //!  Day 10: Pipe Maze
 use anyhow::Result;
 
+use crate::Grid;
+
 /// The direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Direction {
@@ -65,112 +67,87 @@ impl Tile {
 #[derive(Debug, Clone)]
 struct Maze {
     start: Tile,
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
 }
 
 impl Maze {
-    fn parse(input: Vec<&str>) -> Result<Maze> {
-        let mut tiles: Vec<Vec<Tile>> = Vec::new();
-        let mut maze = Maze {
-            start: Tile::new(
-                Default::default(),
-                Default::default(),
-                Direction::NorthWest,
-            ),
-            tiles: Vec::new(),
-        };
-        for (y, line) in input.iter().enumerate() {
-            let mut row: Vec<Tile> = Vec::new();
-            for (x, c) in line.chars().enumerate() {
-                let mut tile = match c {
-                    '-' => Tile::new(
-                        [[x as i32 - 1, y as i32], [x as i32 + 1, y as i32]],
-                        [x as i32, y as i32],
-                        Direction::EastWest,
-                    ),
-                    '.' => Tile::new(
-                        Default::default(),
-                        [x as i32, y as i32],
-                        Direction::NorthWest,
-                    ),
-                    '7' => Tile::new(
-                        [[x as i32, y as i32 + 1], [x as i32 - 1, y as i32]],
-                        [x as i32, y as i32],
-                        Direction::SouthWest,
-                    ),
-                    'F' => Tile::new(
-                        [[x as i32, y as i32 + 1], [x as i32 + 1, y as i32]],
-                        [x as i32, y as i32],
-                        Direction::SouthEast,
-                    ),
-                    'J' => Tile::new(
-                        [[x as i32, y as i32 - 1], [x as i32 - 1, y as i32]],
-                        [x as i32, y as i32],
-                        Direction::NorthWest,
-                    ),
-                    'L' => Tile::new(
-                        [[x as i32, y as i32 - 1], [x as i32 + 1, y as i32]],
-                        [x as i32, y as i32],
-                        Direction::NorthEast,
-                    ),
-                    'S' => Tile::start_tile([x as i32, y as i32]),
-                    '|' => Tile::new(
-                        [[x as i32, y as i32 - 1], [x as i32, y as i32 + 1]],
-                        [x as i32, y as i32],
-                        Direction::SouthNorth,
-                    ),
-                    _ => anyhow::bail!("Invalid character: {}", c),
-                };
-                if tile.is_start {
-                    maze.start = tile;
+    fn parse(input: &str) -> Result<Maze> {
+        let mut tiles = Grid::parse_with_position(input, |(x, y), c| {
+            let (x, y) = (x as i32, y as i32);
+            Ok(match c {
+                '-' => Tile::new(
+                    [[x - 1, y], [x + 1, y]],
+                    [x, y],
+                    Direction::EastWest,
+                ),
+                '.' => {
+                    Tile::new(Default::default(), [x, y], Direction::NorthWest)
                 }
-                if x == 0
-                    || x == line.len() - 1
-                    || y == 0
-                    || y == input.len() - 1
-                {
-                    tile.is_edge = true;
-                }
-                row.push(tile);
+                '7' => Tile::new(
+                    [[x, y + 1], [x - 1, y]],
+                    [x, y],
+                    Direction::SouthWest,
+                ),
+                'F' => Tile::new(
+                    [[x, y + 1], [x + 1, y]],
+                    [x, y],
+                    Direction::SouthEast,
+                ),
+                'J' => Tile::new(
+                    [[x, y - 1], [x - 1, y]],
+                    [x, y],
+                    Direction::NorthWest,
+                ),
+                'L' => Tile::new(
+                    [[x, y - 1], [x + 1, y]],
+                    [x, y],
+                    Direction::NorthEast,
+                ),
+                'S' => Tile::start_tile([x, y]),
+                '|' => Tile::new(
+                    [[x, y - 1], [x, y + 1]],
+                    [x, y],
+                    Direction::SouthNorth,
+                ),
+                _ => anyhow::bail!("Invalid character: {}", c),
+            })
+        })?;
+
+        let (width, height) = (tiles.width(), tiles.height());
+        let mut start = Tile::new(
+            Default::default(),
+            Default::default(),
+            Direction::NorthWest,
+        );
+        for ((x, y), tile) in tiles.iter_mut() {
+            if tile.is_start {
+                start = *tile;
+            }
+            if x == 0 || x == width - 1 || y == 0 || y == height - 1 {
+                tile.is_edge = true;
             }
-            tiles.push(row);
         }
-        maze.tiles = tiles;
+
+        let mut maze = Maze { start, tiles };
         maze.find_start_gates()?;
         Ok(maze)
     }
 
     fn find_start_gates(&mut self) -> Result<()> {
-        // Define the ranges around the start tile
-        let x_range = [
-            (self.start.position[0] - 1).max(0),
-            (self.start.position[0] + 1).min(self.tiles[0].len() as i32 - 1),
-        ];
-        let y_range = [
-            (self.start.position[1] - 1).max(0),
-            (self.start.position[1] + 1).min(self.tiles.len() as i32 - 1),
-        ];
-
-        let mut entries: Vec<Coordinates> = Vec::new();
-
-        // Enumerate over every tile in the tiles Vec that is within our defined
-        // range
-        for i in y_range[0]..=y_range[1] {
-            for j in x_range[0]..=x_range[1] {
-                // Skip the start tile itself
-                if i == self.start.position[1] && j == self.start.position[0] {
-                    continue;
-                }
-                // Check if the tile's list of entries contains the start
-                // position
-                if self.tiles[i as usize][j as usize]
-                    .gates
-                    .contains(&self.start.position)
-                {
-                    entries.push([j, i]);
-                }
-            }
-        }
+        let position = (
+            self.start.position[0] as usize,
+            self.start.position[1] as usize,
+        );
+        // Check which of the tiles around the start tile have a gate at the
+        // start position.
+        let mut entries: Vec<Coordinates> = self
+            .tiles
+            .neighbours8(position)
+            .filter(|&neighbour| {
+                self.tiles[neighbour].gates.contains(&self.start.position)
+            })
+            .map(|(x, y)| [x as i32, y as i32])
+            .collect();
 
         entries.sort();
 
@@ -200,9 +177,7 @@ impl Maze {
             _ => Direction::NorthWest,
         };
         self.start.direction = direction;
-        self.tiles[self.start.position[1] as usize]
-            [self.start.position[0] as usize]
-            .direction = direction;
+        self.tiles[position].direction = direction;
 
         Ok(())
     }
@@ -214,7 +189,7 @@ impl Maze {
         let mut prev = self.start.position;
 
         while next != self.start.position {
-            let tile = &mut self.tiles[next[1] as usize][next[0] as usize];
+            let tile = &mut self.tiles[(next[0] as usize, next[1] as usize)];
             tile.is_loop = true;
             let cur = next;
             next = tile.to(prev);
@@ -227,7 +202,7 @@ impl Maze {
     fn ray_cast_tile(&self, from: Coordinates) -> Result<i32> {
         let mut count = 0;
         for i in 0..from[0] {
-            let tile = &self.tiles[from[1] as usize][i as usize];
+            let tile = &self.tiles[(i as usize, from[1] as usize)];
             if tile.is_loop
                 && (tile.direction == Direction::SouthNorth
                     || tile.direction == Direction::SouthWest
@@ -245,11 +220,9 @@ impl Maze {
 
     fn count_enclosed(&self) -> Result<u32> {
         let mut count = 0;
-        for (y, row) in self.tiles.iter().enumerate() {
-            for (x, tile) in row.iter().enumerate() {
-                if !tile.is_loop && !tile.is_edge {
-                    count += self.ray_cast_tile([x as i32, y as i32])?;
-                }
+        for ((x, y), tile) in self.tiles.iter() {
+            if !tile.is_loop && !tile.is_edge {
+                count += self.ray_cast_tile([x as i32, y as i32])?;
             }
         }
         Ok(count as u32)
@@ -409,8 +382,7 @@ impl Maze {
 /// does it take to get from the starting position to the point farthest from
 /// the starting position?*
 pub fn solve_part_1(input: &str) -> Result<u32> {
-    let lines = input.lines().collect();
-    let mut maze = Maze::parse(lines)?;
+    let mut maze = Maze::parse(input)?;
     let loop_tiles = maze.walk_loop()?;
     Ok((loop_tiles.len() as u32) / 2)
 }
@@ -540,12 +512,56 @@ pub fn solve_part_1(input: &str) -> Result<u32> {
 /// Figure out whether you have time to search for the nest by calculating the
 /// area within the loop. *How many tiles are enclosed by the loop?*
 pub fn solve_part_2(input: &str) -> Result<u32> {
-    let lines = input.lines().collect();
-    let mut maze = Maze::parse(lines)?;
+    let mut maze = Maze::parse(input)?;
     maze.walk_loop()?;
     maze.count_enclosed()
 }
 
+/// The solution for day 10.
+pub struct Day10;
+
+impl crate::Solution for Day10 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Pipe Maze"
+    }
+
+    fn parse(
+        &self,
+        _part: u32,
+        input: &str,
+        _params: &crate::Params,
+    ) -> Result<bool> {
+        Maze::parse(input)?;
+        Ok(true)
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -553,14 +569,14 @@ mod tests {
     #[test]
     fn test_solve_part_1() {
         // Load the file.
-        let input = include_str!("../input/day10.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 7173);
+        let input = crate::test_input!(2023, 10);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 7173);
     }
 
     #[test]
     fn test_solve_part_2() {
         // Load the file.
-        let input = include_str!("../input/day10.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 291);
+        let input = crate::test_input!(2023, 10);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 291);
     }
 }
@@ -2,66 +2,95 @@
 use anyhow::Result;
 use itertools::Itertools;
 
-/// Return the empty lines and columns in the universe.
-fn empty_lines_columns(input: &str) -> Result<(Vec<usize>, Vec<usize>)> {
-    // Find the lines and columns that contain no galaxies.
-    // And expand the universe.
-    let lines = input.lines().collect::<Vec<_>>();
-    let mut empty_lines = Vec::new();
-    let mut columns = Vec::new();
-    for (i, line) in lines.iter().enumerate() {
-        if line.chars().all(|c| c == '.') {
-            empty_lines.push(i);
-        }
+use crate::{Backend, Grid, Params};
 
-        for (j, c) in line.chars().enumerate() {
-            if i == 0 {
-                columns.push(vec![c]);
-            } else {
-                columns[j].push(c);
-            }
+/// Options for the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// How many times larger each empty row or column becomes in part two.
+    /// Defaults to `1000000`, the examples use `10` and `100`.
+    pub multiplier: u64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            multiplier: 1000000,
         }
     }
+}
 
-    // Find the columns that contain no galaxies.
-    let mut empty_columns = Vec::new();
-    for (i, column) in columns.iter().enumerate() {
-        if column.iter().all(|c| *c == '.') {
-            empty_columns.push(i);
-        }
+impl Options {
+    /// The names of the parameters the options are read from.
+    pub const PARAMS: &'static [&'static str] = &["multiplier"];
+
+    /// Read the options from the parameters, using the defaults for any that
+    /// are not set.
+    pub fn from_params(params: &Params) -> Result<Self> {
+        let default = Options::default();
+        Ok(Options {
+            multiplier: params.get_or("multiplier", default.multiplier)?,
+        })
     }
+}
 
-    Ok((empty_lines, empty_columns))
+/// Parse the image of the universe.
+fn parse(input: &str) -> Result<Grid<char>> {
+    Grid::parse(input, |c| match c {
+        '.' | '#' => Ok(c),
+        _ => anyhow::bail!("Invalid character: {}", c),
+    })
 }
 
-/// Expand the universe.
-fn expand_universe(input: &str) -> Result<Vec<String>> {
-    let (empty_lines, empty_columns) = empty_lines_columns(input)?;
-
-    // Expand the universe.
-    let mut expanded: Vec<String> =
-        input.lines().map(|s| s.to_string()).collect();
-    for (index, i) in empty_lines.iter().enumerate() {
-        expanded.insert(
-            index + i,
-            expanded
-                .clone()
-                .get(index + i)
-                .ok_or_else(|| {
-                    anyhow::anyhow!("line does not exist: {}", index + i)
-                })?
-                .to_string(),
-        );
-    }
-    for (index, i) in empty_columns.iter().enumerate() {
-        for (j, line) in expanded.clone().iter().enumerate() {
-            let mut chars = line.chars().collect::<Vec<_>>();
-            chars.insert(index + i, '.');
-            expanded[j] = chars.iter().collect::<String>();
+/// Return the empty lines and columns in the universe.
+fn empty_lines_columns(universe: &Grid<char>) -> (Vec<usize>, Vec<usize>) {
+    // Find the lines and columns that contain no galaxies.
+    let empty_lines = universe
+        .rows()
+        .enumerate()
+        .filter(|(_, row)| row.iter().all(|c| *c == '.'))
+        .map(|(i, _)| i)
+        .collect();
+    let empty_columns = universe
+        .columns()
+        .enumerate()
+        .filter_map(|(i, mut column)| column.all(|c| *c == '.').then(|| i))
+        .collect();
+
+    (empty_lines, empty_columns)
+}
+
+/// Expand the universe, doubling the empty lines and columns.
+fn expand_universe(universe: &Grid<char>) -> Result<Grid<char>> {
+    let (empty_lines, empty_columns) = empty_lines_columns(universe);
+
+    let width = universe.width() + empty_columns.len();
+    let height = universe.height() + empty_lines.len();
+    let mut cells = Vec::with_capacity(width * height);
+    for (i, row) in universe.rows().enumerate() {
+        let mut expanded = Vec::with_capacity(width);
+        for (j, c) in row.iter().enumerate() {
+            expanded.push(*c);
+            if empty_columns.contains(&j) {
+                expanded.push('.');
+            }
+        }
+        if empty_lines.contains(&i) {
+            cells.extend_from_slice(&expanded);
         }
+        cells.extend(expanded);
     }
 
-    Ok(expanded)
+    Grid::new(width, height, cells)
+}
+
+/// The coordinates of the galaxies, as `(line, column)`.
+fn galaxies(universe: &Grid<char>) -> Vec<(usize, usize)> {
+    universe
+        .iter()
+        .filter(|(_, c)| **c == '#')
+        .map(|((x, y), _)| (y, x))
+        .collect()
 }
 
 /// You continue following signs for "Hot Springs" and eventually come across
@@ -193,17 +222,8 @@ fn expand_universe(input: &str) -> Result<Vec<String>> {
 /// Expand the universe, then find the length of the shortest path between every
 /// pair of galaxies. *What is the sum of these lengths?*
 pub fn solve_part_1(input: &str) -> Result<u64> {
-    let expanded = expand_universe(input)?;
-
-    // Get the coordinates of the galaxies.
-    let mut galaxies = Vec::new();
-    for (i, line) in expanded.iter().enumerate() {
-        for (j, c) in line.chars().enumerate() {
-            if c == '#' {
-                galaxies.push((i, j));
-            }
-        }
-    }
+    let expanded = expand_universe(&parse(input)?)?;
+    let galaxies = galaxies(&expanded);
 
     let mut shortest_distances: Vec<u64> = Vec::new();
     for galaxy_pair in galaxies.iter().combinations(2) {
@@ -236,19 +256,16 @@ pub fn solve_part_1(input: &str) -> Result<u64> {
 /// Starting with the same initial image, expand the universe according to these
 /// new rules, then find the length of the shortest path between every pair of
 /// galaxies. *What is the sum of these lengths?*
-pub fn solve_part_2(input: &str, multiplier: u64) -> Result<u64> {
-    let (empty_lines, empty_columns) = empty_lines_columns(input)?;
-    let lines = input.lines().collect::<Vec<_>>();
-
-    // Get the coordinates of the galaxies.
-    let mut galaxies = Vec::new();
-    for (i, line) in lines.iter().enumerate() {
-        for (j, c) in line.chars().enumerate() {
-            if c == '#' {
-                galaxies.push((i, j));
-            }
-        }
-    }
+pub fn solve_part_2(input: &str) -> Result<u64> {
+    solve_part_2_with(input, &Options::default())
+}
+
+/// Solve part two, expanding the universe by the multiplier in the options.
+pub fn solve_part_2_with(input: &str, options: &Options) -> Result<u64> {
+    let multiplier = options.multiplier;
+    let universe = parse(input)?;
+    let (empty_lines, empty_columns) = empty_lines_columns(&universe);
+    let galaxies = galaxies(&universe);
 
     let mut shortest_distances: Vec<u64> = Vec::new();
     for galaxy_pair in galaxies.iter().combinations(2) {
@@ -274,6 +291,46 @@ pub fn solve_part_2(input: &str, multiplier: u64) -> Result<u64> {
     Ok(shortest_distances.iter().sum())
 }
 
+/// The solution for day 11.
+pub struct Day11;
+
+impl crate::Solution for Day11 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Cosmic Expansion"
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        Options::PARAMS
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &Params,
+        _backend: Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        params: &Params,
+        _backend: Backend,
+    ) -> Result<crate::Answer> {
+        let options = Options::from_params(params)?;
+        Ok(solve_part_2_with(input, &options)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -293,8 +350,8 @@ mod tests {
         assert_eq!(super::solve_part_1(input).unwrap(), 374);
 
         // Load the file.
-        let input = include_str!("../input/day11.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 9403026);
+        let input = crate::test_input!(2023, 11);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 9403026);
     }
 
     #[test]
@@ -309,10 +366,12 @@ mod tests {
 ..........
 .......#..
 #...#....."#;
-        assert_eq!(super::solve_part_2(input, 10).unwrap(), 1030);
-        assert_eq!(super::solve_part_2(input, 100).unwrap(), 8410);
+        let options = super::Options { multiplier: 10 };
+        assert_eq!(super::solve_part_2_with(input, &options).unwrap(), 1030);
+        let options = super::Options { multiplier: 100 };
+        assert_eq!(super::solve_part_2_with(input, &options).unwrap(), 8410);
         // Load the file.
-        let input = include_str!("../input/day11.txt");
-        assert_eq!(super::solve_part_2(input, 1000000).unwrap(), 543018317006);
+        let input = crate::test_input!(2023, 11);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 543018317006);
     }
 }
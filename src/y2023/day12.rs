@@ -3,6 +3,39 @@ use anyhow::Result;
 use itertools::Itertools;
 use rayon::prelude::*;
 
+use crate::{Backend, Params};
+
+/// Options for the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// How many copies of each row to unfold in part two. Defaults to `5`.
+    pub unfold: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { unfold: 5 }
+    }
+}
+
+impl Options {
+    /// The names of the parameters the options are read from.
+    pub const PARAMS: &'static [&'static str] = &["unfold"];
+
+    /// Read the options from the parameters, using the defaults for any that
+    /// are not set.
+    pub fn from_params(params: &Params) -> Result<Self> {
+        let default = Options::default();
+        let options = Options {
+            unfold: params.get_or("unfold", default.unfold)?,
+        };
+        if options.unfold == 0 {
+            anyhow::bail!("unfold must be at least 1");
+        }
+        Ok(options)
+    }
+}
+
 /// Spring data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Spring {
@@ -115,11 +148,11 @@ impl SpringRow {
         Ok(SpringRow { springs, groups })
     }
 
-    /// Parse a row of springs, copied 5 times.
-    fn parse_part_2(s: &str) -> Result<SpringRow> {
+    /// Parse a row of springs, unfolded into the given number of copies.
+    fn parse_unfolded(s: &str, copies: usize) -> Result<SpringRow> {
         let mut row = SpringRow::parse(s)?;
 
-        // Replace the list of spring conditions with five copies of itself
+        // Replace the list of spring conditions with copies of itself
         // (separated by ?).
         row.springs = row
             .springs
@@ -127,14 +160,14 @@ impl SpringRow {
             .copied()
             .chain([Spring::Unknown])
             .cycle()
-            .take(row.springs.len() * 5 + 4)
+            .take(row.springs.len() * copies + copies - 1)
             .collect();
         row.groups = row
             .groups
             .iter()
             .copied()
             .cycle()
-            .take(row.groups.len() * 5)
+            .take(row.groups.len() * copies)
             .collect();
 
         Ok(row)
@@ -376,9 +409,14 @@ pub fn solve_part_1(input: &str) -> Result<u32> {
 /// Unfold your condition records; *what is the new sum of possible arrangement
 /// groups?*
 pub fn solve_part_2(input: &str) -> Result<u64> {
+    solve_part_2_with(input, &Options::default())
+}
+
+/// Solve part two, unfolding the rows by the number of copies in the options.
+pub fn solve_part_2_with(input: &str, options: &Options) -> Result<u64> {
     let spring_rows = input
         .lines()
-        .map(|l| SpringRow::parse_part_2(l).unwrap())
+        .map(|l| SpringRow::parse_unfolded(l, options.unfold).unwrap())
         .collect::<Vec<_>>();
 
     let mut arrangements = Vec::new();
@@ -393,6 +431,63 @@ pub fn solve_part_2(input: &str) -> Result<u64> {
     Ok(arrangements.par_iter().sum())
 }
 
+/// The solution for day 12.
+pub struct Day12;
+
+impl crate::Solution for Day12 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Hot Springs"
+    }
+
+    fn backends(&self, _part: u32) -> &'static [Backend] {
+        &[Backend::Rayon]
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        Options::PARAMS
+    }
+
+    fn parse(&self, part: u32, input: &str, params: &Params) -> Result<bool> {
+        let options = Options::from_params(params)?;
+        for line in input.lines() {
+            if part == 1 {
+                SpringRow::parse(line)?;
+            } else {
+                SpringRow::parse_unfolded(line, options.unfold)?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &Params,
+        _backend: Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        params: &Params,
+        _backend: Backend,
+    ) -> Result<crate::Answer> {
+        let options = Options::from_params(params)?;
+        Ok(solve_part_2_with(input, &options)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -408,8 +503,8 @@ mod tests {
         assert_eq!(super::solve_part_1(input).unwrap(), 21);
 
         // Load the file.
-        let input = include_str!("../input/day12.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 8270);
+        let input = crate::test_input!(2023, 12);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 8270);
     }
 
     #[test]
@@ -421,9 +516,12 @@ mod tests {
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1"#;
         assert_eq!(super::solve_part_2(input).unwrap(), 525152);
+        // Unfolding a single copy is the same as part one.
+        let options = super::Options { unfold: 1 };
+        assert_eq!(super::solve_part_2_with(input, &options).unwrap(), 21);
 
         // Load the file.
-        let input = include_str!("../input/day12.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 204640299929836);
+        let input = crate::test_input!(2023, 12);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 204640299929836);
     }
 }
@@ -1,6 +1,8 @@
 //!  Day 13: Point of Incidence
 use anyhow::Result;
 
+use crate::Grid;
+
 /// A terrain data point.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Terrain {
@@ -21,24 +23,9 @@ impl Terrain {
     }
 }
 
-/// A terrain row.
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct TerrainRow(Vec<Terrain>);
-
-impl TerrainRow {
-    /// Parse a terrain row.
-    fn parse(s: &str) -> Result<Self> {
-        let mut row = Vec::with_capacity(s.len());
-        for c in s.chars() {
-            row.push(Terrain::parse(c)?);
-        }
-        Ok(Self(row))
-    }
-}
-
 /// A terrain map.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct TerrainMap(Vec<TerrainRow>);
+struct TerrainMap(Grid<Terrain>);
 
 /// A reflection line.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -52,64 +39,40 @@ enum ReflectionLine {
 impl TerrainMap {
     /// Parse a terrain map.
     fn parse(s: &str) -> Result<Self> {
-        let mut map = Vec::new();
-        for line in s.lines() {
-            map.push(TerrainRow::parse(line)?);
-        }
-        Ok(Self(map))
+        Ok(Self(Grid::parse(s, Terrain::parse)?))
     }
 
     /// Find the line of reflection in the map.
     fn find_line_of_reflection(&self) -> Result<ReflectionLine> {
         // Check all the rows.
-        for (i, row) in self.0.iter().enumerate() {
-            if let Some(next) = self.0.get(i + 1) {
-                if row.0 == next.0 {
-                    // Found a horizontal line.
-                    // Check if all the previous rows are the same.
-
-                    if !is_reflection(self.0.clone(), i) {
-                        continue;
-                    }
-
-                    return Ok(ReflectionLine::Horizontal((
-                        i as u32,
-                        (i as u32) + 1,
-                    )));
-                }
-            }
+        let rows = self.0.rows().collect::<Vec<_>>();
+        if let Some(i) = find_reflection(&rows) {
+            return Ok(ReflectionLine::Horizontal((i, i + 1)));
         }
 
-        let columns: Vec<TerrainRow> = (0..self.0[0].0.len())
-            .map(|i| self.0.iter().map(|c| c.0[i]).collect())
-            .map(TerrainRow)
-            .collect();
-
-        // Check all the columns.
-        for (i, column) in columns.iter().enumerate() {
-            if let Some(next) = columns.get(i + 1) {
-                if column == next {
-                    // Found a vertical line.
-                    // Check if all the previous columns are the same.
-
-                    if !is_reflection(columns.clone(), i) {
-                        continue;
-                    }
-
-                    return Ok(ReflectionLine::Vertical((
-                        i as u32,
-                        (i as u32) + 1,
-                    )));
-                }
-            }
+        // Check all the columns, which are the rows of the transposed map.
+        let transposed = self.0.transpose();
+        let columns = transposed.rows().collect::<Vec<_>>();
+        if let Some(i) = find_reflection(&columns) {
+            return Ok(ReflectionLine::Vertical((i, i + 1)));
         }
 
         anyhow::bail!("no line of reflection found")
     }
 }
 
+/// Find the first line that is the same as the next one, with all the previous
+/// lines reflected too.
+fn find_reflection(lines: &[&[Terrain]]) -> Option<u32> {
+    lines
+        .windows(2)
+        .enumerate()
+        .find(|(i, pair)| pair[0] == pair[1] && is_reflection(lines, *i))
+        .map(|(i, _)| i as u32)
+}
+
 /// Check if the previous rows are the same.
-fn is_reflection(rows: Vec<TerrainRow>, i: usize) -> bool {
+fn is_reflection(rows: &[&[Terrain]], i: usize) -> bool {
     let mut start = i;
     let mut offset = 2;
 
@@ -313,7 +276,42 @@ pub fn solve_part_1(input: &str) -> Result<u32> {
 /// *What number do you get after summarizing the new reflection line in each
 /// pattern in your notes?*
 pub fn solve_part_2(_input: &str) -> Result<u32> {
-    todo!()
+    Err(crate::Unimplemented.into())
+}
+
+/// The solution for day 13.
+pub struct Day13;
+
+impl crate::Solution for Day13 {
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Point of Incidence"
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -340,14 +338,15 @@ mod tests {
         assert_eq!(super::solve_part_1(input).unwrap(), 405);
 
         // Load the file.
-        let input = include_str!("../input/day13.txt");
-        assert_eq!(super::solve_part_1(input).unwrap(), 27742);
+        let input = crate::test_input!(2023, 13);
+        assert_eq!(super::solve_part_1(&input).unwrap(), 27742);
     }
 
     #[test]
+    #[ignore = "not yet solved"]
     fn test_solve_part_2() {
         // Load the file.
-        let input = include_str!("../input/day13.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 0);
+        let input = crate::test_input!(2023, 13);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 0);
     }
 }
@@ -2,82 +2,63 @@
 use std::collections::BTreeMap;
 
 use anyhow::Result;
-use cust::prelude::*;
+
+#[cfg(feature = "cuda")]
+use crate::launch::Launch;
+
+/// A function that runs the graph traversal kernel once, on the GPU or in the
+/// emulator, and returns the nodes and the steps.
+type Traversal =
+    fn(&[(u32, u32)], &[u32], &[u32], &[bool]) -> Result<(Vec<u32>, Vec<u64>)>;
 
 /// Do the graph traversal on the GPU.
+#[cfg(feature = "cuda")]
 fn graph_traversal(
     graph: &[(u32, u32)],
     start: &[u32],
     goals: &[u32],
     directions: &[bool], // true for left, false for right
 ) -> Result<(Vec<u32>, Vec<u64>)> {
-    // Initialize CUDA, this will pick the first available device and will
-    // make a CUDA context from it.
-    // We don't need the context for anything but it must be kept alive.
-    let _ctx = cust::quick_init()?;
+    let length = start.len();
 
-    // Make the CUDA module, modules just house the GPU code for the kernels we
-    // created. they can be made from PTX code, cubins, or fatbins.
-    let module = Module::from_ptx(crate::PTX, &[])?;
+    let mut launch = Launch::new("graph_traversal")?;
+    launch.slice(graph)?;
+    launch.slice(start)?;
+    launch.slice(goals)?;
+    launch.slice(directions)?;
+    let nodes = launch.output::<u32>(length)?;
+    let steps = launch.output::<u64>(length)?;
+    let outputs = unsafe { launch.run(length)? };
 
-    // make a CUDA stream to issue calls to. You can think of this as an OS
-    // thread but for dispatching GPU calls.
-    let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
+    // copy back the data from the GPU.
+    let nodes = outputs.get(nodes)?;
+    let steps = outputs.get(steps)?;
 
-    // allocate the GPU memory needed to house our numbers and copy them over.
-    let graph_gpu = graph.as_dbuf()?;
-    let start_gpu = start.as_dbuf()?;
-    let goals_gpu = goals.as_dbuf()?;
-    let directions_gpu = directions.as_dbuf()?;
+    Ok((nodes, steps))
+}
 
+/// Do the graph traversal on the host, running the kernel in the emulator.
+fn graph_traversal_emulated(
+    graph: &[(u32, u32)],
+    start: &[u32],
+    goals: &[u32],
+    directions: &[bool], // true for left, false for right
+) -> Result<(Vec<u32>, Vec<u64>)> {
     let length = start.len();
 
-    // Allocate our output buffer. You could also use
-    // DeviceBuffer::uninitialized() to avoid the cost of the copy, but you
-    // need to be careful not to read from the buffer.
     let mut nodes = vec![0u32; length];
-    let nodes_buf = nodes.as_slice().as_dbuf()?;
-
     let mut steps = vec![0u64; length];
-    let steps_buf = steps.as_slice().as_dbuf()?;
-
-    // Retrieve the kernel from the module so we can calculate the right
-    // launch config.
-    let func = module.get_function("graph_traversal")?;
-
-    // Use the CUDA occupancy API to find an optimal launch configuration for
-    // the grid and block size. This will try to maximize how much of the
-    // GPU is used by finding the best launch configuration for the
-    // current CUDA device/architecture.
-    let (_, block_size) = func.suggested_launch_configuration(0, 0.into())?;
 
+    let block_size = gpu::emulator::DEFAULT_BLOCK_SIZE;
     let grid_size = (length as u32 + block_size - 1) / block_size;
 
-    // Actually launch the GPU kernel. This will queue up the launch on the
-    // stream, it will not block the thread until the kernel is finished.
-    unsafe {
-        launch!(
-            // slices are passed as two parameters, the pointer and the length.
-            func<<<grid_size, block_size, 0, stream>>>(
-                graph_gpu.as_device_ptr(),
-                graph_gpu.len(),
-                start_gpu.as_device_ptr(),
-                start_gpu.len(),
-                goals_gpu.as_device_ptr(),
-                goals_gpu.len(),
-                directions_gpu.as_device_ptr(),
-                directions_gpu.len(),
-                nodes_buf.as_device_ptr(),
-                steps_buf.as_device_ptr(),
-            )
-        )?;
-    }
-
-    stream.synchronize()?;
-
-    // copy back the data from the GPU.
-    nodes_buf.copy_to(&mut nodes)?;
-    steps_buf.copy_to(&mut steps)?;
+    let nodes_ptr = nodes.as_mut_ptr();
+    let steps_ptr = steps.as_mut_ptr();
+    gpu::emulator::launch(grid_size, block_size, || unsafe {
+        gpu::y2023::day08::graph_traversal(
+            graph, start, goals, directions, nodes_ptr, steps_ptr,
+        )
+    });
 
     Ok((nodes, steps))
 }
@@ -138,9 +119,20 @@ fn graph_traversal(
 ///
 /// Simultaneously start on every node that ends with `A`. *How many steps does
 /// it take before you're only on nodes that end with `Z`?*
+#[cfg(feature = "cuda")]
 pub fn solve_part_2(input: &str) -> Result<u64> {
+    solve_part_2_using(input, graph_traversal)
+}
+
+/// Solve part two on the host, running the kernel in the emulator.
+pub fn solve_part_2_emulated(input: &str) -> Result<u64> {
+    solve_part_2_using(input, graph_traversal_emulated)
+}
+
+/// Solve part two, running the kernel with the given traversal.
+fn solve_part_2_using(input: &str, traversal: Traversal) -> Result<u64> {
     let (directions, instructions) =
-        crate::day08::parse_directions_and_instructions(input)?;
+        crate::y2023::day08::parse_directions_and_instructions(input)?;
 
     // Create the graph.
     let instruction_keys = instructions
@@ -180,8 +172,8 @@ pub fn solve_part_2(input: &str) -> Result<u64> {
     let directions: Vec<bool> = directions
         .iter()
         .map(|direction| match direction {
-            crate::day08::Direction::Left => true,
-            crate::day08::Direction::Right => false,
+            crate::y2023::day08::Direction::Left => true,
+            crate::y2023::day08::Direction::Right => false,
         })
         .collect::<Vec<_>>();
 
@@ -202,7 +194,7 @@ pub fn solve_part_2(input: &str) -> Result<u64> {
     let mut steps = 0;
     loop {
         let (nodes, steps_run) =
-            graph_traversal(&graph, &start, &goals, &directions)?;
+            traversal(&graph, &start, &goals, &directions)?;
 
         steps += steps_run.len() as u64;
 
@@ -221,6 +213,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[test]
+    #[cfg(feature = "cuda")]
     fn test_solve_part_2() {
         let input = r#"LR
 
@@ -235,7 +228,24 @@ XXX = (XXX, XXX)"#;
         assert_eq!(super::solve_part_2(input).unwrap(), 6);
 
         // Load the file.
-        let input = include_str!("../input/day08.txt");
-        assert_eq!(super::solve_part_2(input).unwrap(), 15726453850399);
+        let input = crate::test_input!(2023, 8);
+        assert_eq!(super::solve_part_2(&input).unwrap(), 15726453850399);
+    }
+
+    #[test]
+    fn test_solve_part_2_emulated() {
+        // Only the example, the emulator runs every thread one after another
+        // so the real input would take far too long.
+        let input = r#"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"#;
+        assert_eq!(super::solve_part_2_emulated(input).unwrap(), 6);
     }
 }
@@ -0,0 +1,53 @@
+//!  Day {{day:02}}: {{title}}
+
+use anyhow::Result;
+
+/// {{part_one}}
+{{part_one_fn}}
+
+/// {{part_two}}
+{{part_two_fn}}
+
+/// The solution for day {{day:02}}.
+pub struct Day{{day:02}};
+
+impl crate::Solution for Day{{day:02}} {
+    fn year(&self) -> u32 {
+        {{year}}
+    }
+
+    fn day(&self) -> u32 {
+        {{day}}
+    }
+
+    fn title(&self) -> &'static str {
+        {{title_literal}}
+    }
+
+    fn part_1(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_1(input)?.into())
+    }
+
+    fn part_2(
+        &self,
+        input: &str,
+        _params: &crate::Params,
+        _backend: crate::Backend,
+    ) -> Result<crate::Answer> {
+        Ok(solve_part_2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+{{part_one_test}}
+
+{{part_two_test}}
+}
@@ -0,0 +1,76 @@
+//!  Solve for day {{day:02}}: {{title}} using a GPU.
+use anyhow::Result;
+
+#[cfg(feature = "cuda")]
+use crate::launch::Launch;
+
+/// A function that runs the kernel once, on the GPU or in the emulator, and
+/// returns the output.
+type Kernel = fn(&[u32]) -> Result<Vec<u32>>;
+
+/// Run the kernel on the GPU.
+#[cfg(feature = "cuda")]
+fn run_kernel(input: &[u32]) -> Result<Vec<u32>> {
+    let mut launch = Launch::new("day{{day:02}}_kernel")?;
+    launch.slice(input)?;
+    let output = launch.output::<u32>(input.len())?;
+    let outputs = unsafe { launch.run(input.len())? };
+
+    // copy back the data from the GPU.
+    outputs.get(output)
+}
+
+/// Run the kernel on the host, in the emulator.
+fn run_kernel_emulated(input: &[u32]) -> Result<Vec<u32>> {
+    let length = input.len();
+
+    let mut output = vec![0u32; length];
+
+    let block_size = gpu::emulator::DEFAULT_BLOCK_SIZE;
+    let grid_size = (length as u32 + block_size - 1) / block_size;
+
+    let output_ptr = output.as_mut_ptr();
+    gpu::emulator::launch(grid_size, block_size, || unsafe {
+        gpu::y{{year}}::day{{day:02}}::day{{day:02}}_kernel(input, output_ptr)
+    });
+
+    Ok(output)
+}
+
+/// Solve part one on the GPU.
+#[cfg(feature = "cuda")]
+pub fn solve_part_1(input: &str) -> Result<u64> {
+    solve_part_1_using(input, run_kernel)
+}
+
+/// Solve part one on the host, running the kernel in the emulator.
+pub fn solve_part_1_emulated(input: &str) -> Result<u64> {
+    solve_part_1_using(input, run_kernel_emulated)
+}
+
+/// Solve part one, running the kernel with the given function.
+fn solve_part_1_using(_input: &str, _kernel: Kernel) -> Result<u64> {
+    Err(crate::Unimplemented.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[cfg(feature = "cuda")]
+    #[ignore = "not yet solved"]
+    fn test_solve_part_1() {
+        // Load the file.
+        let input = crate::test_input!({{year}}, {{day}});
+        assert_eq!(super::solve_part_1(&input).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore = "not yet solved"]
+    fn test_solve_part_1_emulated() {
+        // Load the file.
+        let input = crate::test_input!({{year}}, {{day}});
+        assert_eq!(super::solve_part_1_emulated(&input).unwrap(), 0);
+    }
+}
@@ -0,0 +1,16 @@
+//!  CUDA Kernel for day {{day:02}}: {{title}}
+
+use cuda_std::kernel;
+
+use crate::thread;
+
+/// Run a thread for each item of the input.
+#[kernel]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn day{{day:02}}_kernel(input: &[u32], output: *mut u32) {
+    let idx = thread::index_1d() as usize;
+    if idx < input.len() {
+        let elem = &mut *output.add(idx);
+        *elem = input[idx];
+    }
+}
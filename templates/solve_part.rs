@@ -0,0 +1,3 @@
+pub fn solve_part_{{part}}(_input: &str) -> Result<{{answer_type}}> {
+    Err(crate::Unimplemented.into())
+}
@@ -0,0 +1,8 @@
+    #[test]
+    #[ignore = "not yet solved"]
+    fn test_solve_part_{{part}}() {
+{{example}}
+        // Load the file.
+        let input = crate::test_input!({{year}}, {{day}});
+        assert_eq!(super::solve_part_{{part}}(&input).unwrap(), 0);
+    }